@@ -0,0 +1,180 @@
+#![no_std]
+#![no_main]
+
+// #[cfg(not(target_arch = "wasm32"))]
+// compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+// ERC20 token for the contract tests that burns FEE_PERCENT of every
+// transfer, so recipients get less than the amount sent
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String, vec};
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    contracts::{EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, NamedKeys},
+    ApiError, CLType, CLTyped, CLValue, Parameter, U256,
+};
+
+use lock_staking::{utils, Address};
+
+const FEE_PERCENT: u64 = 1;
+
+const KEY_NAME_BALANCES: &str = "balances";
+const KEY_NAME_ALLOWANCES: &str = "allowances";
+
+type Balances = BTreeMap<Address, U256>;
+type Allowances = BTreeMap<(Address, Address), U256>;
+
+#[no_mangle]
+pub extern "C" fn balance_of() {
+    let address: Address = runtime::get_named_arg("address");
+
+    let balances: Balances = utils::get_key(KEY_NAME_BALANCES);
+    let balance = balances.get(&address).copied().unwrap_or_default();
+
+    runtime::ret(CLValue::from_t(balance).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn decimals() {
+    runtime::ret(CLValue::from_t(9u8).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn approve() {
+    let spender: Address = runtime::get_named_arg("spender");
+    let amount: U256 = runtime::get_named_arg("amount");
+
+    let mut allowances: Allowances = utils::get_key(KEY_NAME_ALLOWANCES);
+    allowances.insert((utils::get_immediate_caller(), spender), amount);
+    utils::set_key(KEY_NAME_ALLOWANCES, allowances);
+}
+
+#[no_mangle]
+pub extern "C" fn transfer() {
+    let recipient: Address = runtime::get_named_arg("recipient");
+    let amount: U256 = runtime::get_named_arg("amount");
+
+    move_tokens(utils::get_immediate_caller(), recipient, amount);
+}
+
+#[no_mangle]
+pub extern "C" fn transfer_from() {
+    let owner: Address = runtime::get_named_arg("owner");
+    let recipient: Address = runtime::get_named_arg("recipient");
+    let amount: U256 = runtime::get_named_arg("amount");
+
+    let mut allowances: Allowances = utils::get_key(KEY_NAME_ALLOWANCES);
+    let allowance = allowances
+        .entry((owner, utils::get_immediate_caller()))
+        .or_default();
+    *allowance = allowance
+        .checked_sub(amount)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    utils::set_key(KEY_NAME_ALLOWANCES, allowances);
+
+    move_tokens(owner, recipient, amount);
+}
+
+// takes amount from owner and burns the fee out of it
+fn move_tokens(owner: Address, recipient: Address, amount: U256) {
+    let mut balances: Balances = utils::get_key(KEY_NAME_BALANCES);
+
+    let balance = balances.entry(owner).or_default();
+    *balance = balance
+        .checked_sub(amount)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let fee = amount * U256::from(FEE_PERCENT) / U256::from(100);
+
+    let balance = balances.entry(recipient).or_default();
+    *balance = balance
+        .checked_add(amount - fee)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    utils::set_key(KEY_NAME_BALANCES, balances);
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let total_supply: U256 = runtime::get_named_arg("total_supply");
+
+    let mut balances = Balances::new();
+    balances.insert(Address::from(runtime::get_caller()), total_supply);
+
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(
+        String::from(KEY_NAME_BALANCES),
+        storage::new_uref(balances).into(),
+    );
+    named_keys.insert(
+        String::from(KEY_NAME_ALLOWANCES),
+        storage::new_uref(Allowances::new()).into(),
+    );
+
+    let mut entry_points = EntryPoints::new();
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "balance_of",
+        vec![Parameter::new("address", Address::cl_type())],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "decimals",
+        vec![],
+        u8::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "approve",
+        vec![
+            Parameter::new("spender", Address::cl_type()),
+            Parameter::new("amount", U256::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "transfer",
+        vec![
+            Parameter::new("recipient", Address::cl_type()),
+            Parameter::new("amount", U256::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "transfer_from",
+        vec![
+            Parameter::new("owner", Address::cl_type()),
+            Parameter::new("recipient", Address::cl_type()),
+            Parameter::new("amount", U256::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    let (contract_hash, _) = storage::new_contract(
+        entry_points,
+        Some(named_keys),
+        Some(String::from("fee_token_package")),
+        None,
+    );
+
+    runtime::put_key("fee_token_contract", contract_hash.into());
+}
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use std::{convert::TryInto, path::PathBuf};
+    use std::{collections::BTreeMap, convert::TryInto, path::PathBuf};
 
     use casper_engine_test_support::{
         DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, ARG_AMOUNT,
@@ -8,8 +8,9 @@ mod tests {
         DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
         MINIMUM_ACCOUNT_CREATION_BALANCE,
     };
-    use casper_execution_engine::core::engine_state::{
-        run_genesis_request::RunGenesisRequest, GenesisAccount,
+    use casper_execution_engine::core::{
+        engine_state::{self, run_genesis_request::RunGenesisRequest, GenesisAccount},
+        execution,
     };
     use casper_types::{
         account::{Account, AccountHash},
@@ -18,8 +19,8 @@ mod tests {
         contracts::NamedKeys,
         runtime_args,
         system::mint,
-        AsymmetricType, CLType, CLTyped, ContractHash, ContractPackageHash, HashAddr, Key, Motes,
-        PublicKey, RuntimeArgs, SecretKey, StoredValue, URef, U256, U512,
+        ApiError, AsymmetricType, CLType, CLTyped, ContractHash, ContractPackageHash, HashAddr,
+        Key, Motes, PublicKey, RuntimeArgs, SecretKey, StoredValue, URef, U256, U512,
     };
 
     use casper_engine_test_support::WasmTestBuilder;
@@ -35,6 +36,9 @@ mod tests {
 
     const STAKE_CONTRACT_WASM: &str = "stake_contract.wasm";
     const TOKEN_WASM: &str = "erc20_token.wasm";
+    const STAKE_PORTAL_WASM: &str = "stake_wasm_portal.wasm";
+    const FEE_TOKEN_WASM: &str = "fee_token.wasm";
+    const FEE_TOKEN_CONTRACT_KEY: &str = "fee_token_contract";
     const ERC20_TOKEN_CONTRACT_KEY: &str = "erc20_token_contract";
 
     const STAKE_HASH_KEY: &str = "stake_conthash";
@@ -45,6 +49,12 @@ mod tests {
     const KEY_NAME_DIC_STAKE_INFOS: &str = "dic_stake_infos";
     const KEY_NAME_USER_INFOS: &str = "user_infos";
     const ADMIN_KEY: &str = "admin-account";
+    const REWARD_PURSE_KEY: &str = "reward_purse_";
+    const KEY_NAME_USER_CHECKPOINTS: &str = "user_checkpoints";
+    const KEY_NAME_POOL_CHECKPOINTS: &str = "pool_checkpoints";
+    const KEY_NAME_POOL_METADATA: &str = "pool_metadata";
+    const KEY_NAME_POOL_CONTRACTS: &str = "pool_contracts";
+    const POOL_CONTRACT_COUNT_KEY: &str = "pool_contract_count";
 
     pub type TestContext = (
         WasmTestBuilder<InMemoryGlobalState>,
@@ -1055,6 +1065,121 @@ mod tests {
         assert_eq!(balance, supply - pool.total_reward);
     }
 
+    #[test]
+    fn add_pool_auto_precision_works() {
+        let (mut builder, stake_contract, stake_contract_package_key, erc20_token, _, _) = setup();
+
+        // precision 0 lets the contract pick it from the token decimals
+        let add_pool = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "add_pool",
+            runtime_args! {
+                "staking_token" => erc20_token.to_formatted_string(),
+                "reward_token" => erc20_token.to_formatted_string(),
+                "start_time" => 0u64,
+                "end_time" => 100u64,
+                "precision" => 0u64,
+                "total_reward" => U256::from(1000u64)
+            },
+        )
+        .build();
+
+        builder.exec(add_pool).commit().expect_success();
+
+        let pool = get_pool_by_id(stake_contract, stake_contract_package_key, 1_u64, &builder);
+
+        // 9 decimal staking token + 18
+        assert_eq!(pool.precision, 27u64);
+
+        // an explicit precision is kept as is
+        let pool = get_pool(stake_contract, stake_contract_package_key, &builder);
+        assert_eq!(pool.precision, 18u64);
+    }
+
+    #[test]
+    fn add_pool_rejects_reversed_window() {
+        let (mut builder, stake_contract, _, erc20_token, _, _) = setup();
+
+        let add_pool = |start_time: u64, end_time: u64| {
+            ExecuteRequestBuilder::contract_call_by_hash(
+                *DEFAULT_ACCOUNT_ADDR,
+                stake_contract,
+                "add_pool",
+                runtime_args! {
+                    "staking_token" => erc20_token.to_formatted_string(),
+                    "reward_token" => erc20_token.to_formatted_string(),
+                    "start_time" => start_time,
+                    "end_time" => end_time,
+                    "precision" => 18u64,
+                    "total_reward" => U256::from(1000u64)
+                },
+            )
+            .with_block_time(0)
+            .build()
+        };
+
+        // the pool has to end after it starts
+        builder.exec(add_pool(100, 50)).commit().expect_failure();
+        builder.exec(add_pool(100, 100)).commit().expect_failure();
+
+        builder.exec(add_pool(100, 101)).commit().expect_success();
+
+        let index: u64 = builder.get_value(stake_contract, INDEX_KEY);
+        assert_eq!(index, 2_u64);
+    }
+
+    #[test]
+    fn pool_metadata_works() {
+        let (mut builder, stake_contract, _, erc20_token, _, _) = setup();
+
+        // name, description and url are optional args of add_pool
+        let add_pool = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "add_pool",
+            runtime_args! {
+                "staking_token" => erc20_token.to_formatted_string(),
+                "reward_token" => erc20_token.to_formatted_string(),
+                "start_time" => 0u64,
+                "end_time" => 100u64,
+                "precision" => 18u64,
+                "total_reward" => U256::from(1000u64),
+                "name" => String::from("SWAP farm"),
+                "url" => String::from("https://example.com")
+            },
+        )
+        .build();
+
+        builder.exec(add_pool).commit().expect_success();
+
+        let metadata = get_pool_metadata(stake_contract, 1u64, &builder);
+        assert_eq!(metadata["name"], "SWAP farm");
+        assert_eq!(metadata["description"], "");
+        assert_eq!(metadata["url"], "https://example.com");
+
+        // the owner can edit it later
+        let set_metadata = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "set_pool_metadata",
+            runtime_args! {
+                "pool_id" => 0u64,
+                "name" => String::from("SWAP staking"),
+                "description" => String::from("Stake SWAP, earn SWAP"),
+                "url" => String::new()
+            },
+        )
+        .build();
+
+        builder.exec(set_metadata).commit().expect_success();
+
+        let metadata = get_pool_metadata(stake_contract, 0u64, &builder);
+        assert_eq!(metadata["name"], "SWAP staking");
+        assert_eq!(metadata["description"], "Stake SWAP, earn SWAP");
+        assert_eq!(metadata["url"], "");
+    }
+
     #[test]
     fn deposit_works() {
         let (mut builder, stake_contract, stake_contract_package_key, erc20_token, account, supply) =
@@ -1308,6 +1433,113 @@ mod tests {
         assert_eq!(balance, TOTAL_REWARD / U256::from(2));
     }
 
+    #[test]
+    fn stop_reward_different_tokens_works() {
+        let (
+            mut builder,
+            stake_contract,
+            stake_contract_package_key,
+            erc20_tokens,
+            accounts,
+            supply,
+        ) = setup_multiple_users_different_tokens();
+
+        let TOTAL_REWARD = supply.checked_div(U256::from(2)).unwrap();
+        let amount_to_deposit = U256::from(100_000_000_000_u64);
+
+        let staking_balance_pre = get_token_balance(&accounts[0], erc20_tokens[0], &builder);
+        let reward_balance_pre = get_token_balance(&accounts[0], erc20_tokens[1], &builder);
+
+        // call deposit()
+        let deposit_req = ExecuteRequestBuilder::contract_call_by_hash(
+            accounts[2].account_hash(),
+            stake_contract,
+            "deposit",
+            runtime_args! {
+                "pool_id" => 0u64,
+                "amount" => amount_to_deposit,
+            },
+        )
+        .with_block_time(100)
+        .build();
+
+        builder.exec(deposit_req).commit().expect_success();
+
+        // only the pool owner can stop the pool
+        let stop_reward_not_owner = ExecuteRequestBuilder::contract_call_by_hash(
+            accounts[2].account_hash(),
+            stake_contract,
+            "stop_reward",
+            runtime_args! {
+                "pool_id" => 0u64,
+            },
+        )
+        .with_block_time(150)
+        .build();
+
+        builder
+            .exec(stop_reward_not_owner)
+            .commit()
+            .expect_failure();
+
+        // stop halfway through the pool
+        let stop_reward_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "stop_reward",
+            runtime_args! {
+                "pool_id" => 0u64,
+            },
+        )
+        .with_block_time(150)
+        .build();
+
+        builder.exec(stop_reward_req).commit().expect_success();
+
+        let pool = get_pool(stake_contract, stake_contract_package_key, &builder);
+
+        // pool budget shrinks to what was emitted
+        assert_eq!(pool.end_time, 150_u64);
+        assert_eq!(pool.total_reward, TOTAL_REWARD / U256::from(2));
+        assert_eq!(pool.emitted_reward, pool.total_reward);
+        assert_eq!(pool.total_staked, amount_to_deposit);
+
+        // unemitted half refunded in the reward token
+        let reward_balance = get_token_balance(&accounts[0], erc20_tokens[1], &builder);
+        assert_eq!(
+            reward_balance,
+            reward_balance_pre + TOTAL_REWARD / U256::from(2)
+        );
+
+        // staking tokens of stakers are untouched
+        let staking_balance = get_token_balance(&accounts[0], erc20_tokens[0], &builder);
+        assert_eq!(staking_balance, staking_balance_pre);
+
+        // staker still gets everything emitted before the stop
+        let withdraw_req = ExecuteRequestBuilder::contract_call_by_hash(
+            accounts[2].account_hash(),
+            stake_contract,
+            "withdraw",
+            runtime_args! {
+                "pool_id" => 0u64,
+                "amount" => amount_to_deposit
+            },
+        )
+        .with_block_time(180)
+        .build();
+
+        builder.exec(withdraw_req).commit().expect_success();
+
+        let staker_reward = get_token_balance(&accounts[2], erc20_tokens[1], &builder);
+
+        // allow 1e-6 of dust
+        assert!(staker_reward <= TOTAL_REWARD / U256::from(2));
+        assert!(staker_reward >= TOTAL_REWARD / U256::from(2) - U256::from(1_000));
+
+        let staking_balance = get_token_balance(&accounts[2], erc20_tokens[0], &builder);
+        assert_eq!(staking_balance, amount_to_deposit);
+    }
+
     #[test]
     fn emergency_withdraw_works() {
         let (mut builder, stake_contract, stake_contract_package_key, erc20_token, account, supply) =
@@ -1356,6 +1588,20 @@ mod tests {
         assert_eq!(balance, supply - TOTAL_REWARD - TOTAL_REWARD);
         assert_eq!(user_info.amount, pool.total_staked);
 
+        let set_emergency_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "set_emergency",
+            runtime_args! {
+                "pool_id" => 0u64,
+                "emergency" => true,
+            },
+        )
+        .with_block_time(50)
+        .build();
+
+        builder.exec(set_emergency_req).commit().expect_success();
+
         let emergency_withdraw_req = ExecuteRequestBuilder::contract_call_by_hash(
             *DEFAULT_ACCOUNT_ADDR,
             stake_contract,
@@ -1381,12 +1627,1239 @@ mod tests {
 
         balance = get_token_balance(&account, erc20_token, &builder);
 
-        // balance returned no reward
-        assert_eq!(balance, TOTAL_REWARD);
+        // balance returned no reward
+        assert_eq!(balance, TOTAL_REWARD);
+    }
+
+    #[test]
+    fn set_admin_works() {
+        let (
+            mut builder,
+            stake_contract,
+            stake_contract_package_key,
+            erc20_tokens,
+            accounts,
+            supply,
+        ) = setup_multiple_users();
+
+        let mut admin: AccountHash = builder.get_value(stake_contract, ADMIN_KEY);
+
+        assert_eq!(admin, accounts[0].account_hash());
+
+        // call set_admin()
+        let mut set_admin_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "set_admin",
+            runtime_args! {
+                "new_admin" => accounts[1].account_hash(),
+            },
+        )
+        .with_block_time(20)
+        .build();
+
+        builder.exec(set_admin_req).commit().expect_success();
+
+        admin = builder.get_value(stake_contract, ADMIN_KEY);
+
+        // new admin set successfully
+        assert_eq!(admin, accounts[1].account_hash());
+
+        // old admin no longer can call set_admin
+        set_admin_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "set_admin",
+            runtime_args! {
+                "new_admin" => accounts[3].account_hash(),
+            },
+        )
+        .with_block_time(21)
+        .build();
+
+        builder.exec(set_admin_req).commit().expect_failure();
+
+        // new admin can now call set_admin
+        set_admin_req = ExecuteRequestBuilder::contract_call_by_hash(
+            accounts[1].account_hash(),
+            stake_contract,
+            "set_admin",
+            runtime_args! {
+                "new_admin" => accounts[3].account_hash(),
+            },
+        )
+        .with_block_time(22)
+        .build();
+
+        builder.exec(set_admin_req).commit().expect_success();
+
+        admin = builder.get_value(stake_contract, ADMIN_KEY);
+
+        // new admin set successfully
+        assert_eq!(admin, accounts[3].account_hash());
+    }
+
+    #[test]
+    fn withdraw_works() {
+        let (mut builder, stake_contract, stake_contract_package_key, erc20_token, account, supply) =
+            setup();
+
+        let TOTAL_REWARD = supply.checked_div(U256::from(2)).unwrap();
+        // index is now 1
+        let index: u64 = builder.get_value(stake_contract, INDEX_KEY);
+        assert_eq!(index, 1_u64);
+
+        let mut pool = get_pool(stake_contract, stake_contract_package_key, &builder);
+        // verify values of input pool
+        assert_eq!(pool.start_time, 0u64);
+        assert_eq!(pool.end_time, 100u64);
+        assert_eq!(pool.total_reward, TOTAL_REWARD);
+        assert_eq!(pool.reward_token, pool.staking_token);
+        assert_eq!(pool.owner, *DEFAULT_ACCOUNT_ADDR);
+
+        let mut balance = get_token_balance(&account, erc20_token, &builder);
+
+        assert_eq!(balance, supply - TOTAL_REWARD);
+
+        // call deposit()
+        let deposit_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "deposit",
+            runtime_args! {
+                "pool_id" => 0u64,
+                "amount" => TOTAL_REWARD,
+            },
+        )
+        .with_block_time(0)
+        .build();
+
+        builder.exec(deposit_req).commit().expect_success();
+
+        pool = get_pool(stake_contract, stake_contract_package_key, &builder);
+        // verify values of pool after deposit
+        assert_eq!(pool.total_staked, TOTAL_REWARD);
+
+        // check balance is deducted
+        balance = get_token_balance(&account, erc20_token, &builder);
+
+        assert_eq!(balance, supply - TOTAL_REWARD - TOTAL_REWARD);
+
+        // call withdraw() after fully finished
+        let withdraw_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "withdraw",
+            runtime_args! {
+                "pool_id" => 0u64,
+                "amount" => TOTAL_REWARD
+            },
+        )
+        .with_block_time(101)
+        .build();
+
+        builder.exec(withdraw_req).commit().expect_success();
+
+        pool = get_pool(stake_contract, stake_contract_package_key, &builder);
+
+        // verify all tokens withdrawn
+        assert_eq!(pool.total_staked, U256::zero());
+
+        // check balance is added
+        balance = get_token_balance(&account, erc20_token, &builder);
+
+        // Should have all tokens back
+        assert_eq!(balance, supply);
+    }
+
+    #[test]
+    fn batch_operations_work() {
+        let (mut builder, stake_contract, stake_contract_package_key, erc20_token, account, supply) =
+            setup();
+
+        let TOTAL_REWARD = supply.checked_div(U256::from(2)).unwrap();
+        let SECOND_REWARD = U256::from(1000u64);
+        let amount_to_deposit = U256::from(1000u64);
+
+        // second pool in the same token
+        let add_pool = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "add_pool",
+            runtime_args! {
+                "staking_token" => erc20_token.to_formatted_string(),
+                "reward_token" => erc20_token.to_formatted_string(),
+                "start_time" => 0u64,
+                "end_time" => 100u64,
+                "precision" => 18u64,
+                "total_reward" => SECOND_REWARD
+            },
+        )
+        .build();
+
+        builder.exec(add_pool).commit().expect_success();
+
+        for pool_id in [0u64, 1u64] {
+            let deposit_req = ExecuteRequestBuilder::contract_call_by_hash(
+                *DEFAULT_ACCOUNT_ADDR,
+                stake_contract,
+                "deposit",
+                runtime_args! {
+                    "pool_id" => pool_id,
+                    "amount" => amount_to_deposit,
+                },
+            )
+            .with_block_time(0)
+            .build();
+
+            builder.exec(deposit_req).commit().expect_success();
+        }
+
+        let mut balance = get_token_balance(&account, erc20_token, &builder);
+        assert_eq!(
+            balance,
+            supply - TOTAL_REWARD - SECOND_REWARD - amount_to_deposit * 2
+        );
+
+        // claim half of both pools' rewards at once
+        let harvest_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "harvest_many",
+            runtime_args! {
+                "pool_ids" => vec![0u64, 1u64],
+            },
+        )
+        .with_block_time(50)
+        .build();
+
+        builder.exec(harvest_req).commit().expect_success();
+
+        balance = get_token_balance(&account, erc20_token, &builder);
+        assert_eq!(
+            balance,
+            supply - TOTAL_REWARD / 2 - SECOND_REWARD / 2 - amount_to_deposit * 2
+        );
+
+        // anyone can bring pools up to date
+        let update_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "mass_update_pools",
+            runtime_args! {
+                "pool_ids" => vec![0u64, 1u64],
+            },
+        )
+        .with_block_time(75)
+        .build();
+
+        builder.exec(update_req).commit().expect_success();
+
+        for pool_id in [0u64, 1u64] {
+            let pool = get_pool_by_id(
+                stake_contract,
+                stake_contract_package_key,
+                pool_id,
+                &builder,
+            );
+            assert_eq!(pool.last_reward_timestamp, 75u64);
+        }
+
+        // amounts must line up with pool ids
+        let bad_withdraw_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "withdraw_many",
+            runtime_args! {
+                "pool_ids" => vec![0u64, 1u64],
+                "amounts" => vec![amount_to_deposit],
+            },
+        )
+        .with_block_time(101)
+        .build();
+
+        builder.exec(bad_withdraw_req).commit().expect_failure();
+
+        // withdraw everything from both pools after they end
+        let withdraw_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "withdraw_many",
+            runtime_args! {
+                "pool_ids" => vec![0u64, 1u64],
+                "amounts" => vec![amount_to_deposit, amount_to_deposit],
+            },
+        )
+        .with_block_time(101)
+        .build();
+
+        builder.exec(withdraw_req).commit().expect_success();
+
+        for pool_id in [0u64, 1u64] {
+            let pool = get_pool_by_id(
+                stake_contract,
+                stake_contract_package_key,
+                pool_id,
+                &builder,
+            );
+            assert_eq!(pool.total_staked, U256::zero());
+
+            let user_info = get_user_info_by_id(stake_contract, &account, pool_id, &builder);
+            assert_eq!(user_info.amount, U256::zero());
+        }
+
+        // all stake and rewards are back
+        balance = get_token_balance(&account, erc20_token, &builder);
+        assert_eq!(balance, supply);
+    }
+
+    #[test]
+    fn stake_checkpoints_work() {
+        let (mut builder, stake_contract, stake_contract_package_key, _, account, _) = setup();
+
+        let deposit = |amount: u64, block_time: u64| {
+            ExecuteRequestBuilder::contract_call_by_hash(
+                *DEFAULT_ACCOUNT_ADDR,
+                stake_contract,
+                "deposit",
+                runtime_args! {
+                    "pool_id" => 0u64,
+                    "amount" => U256::from(amount),
+                },
+            )
+            .with_block_time(block_time)
+            .build()
+        };
+
+        builder.exec(deposit(1000, 10)).commit().expect_success();
+        builder.exec(deposit(500, 20)).commit().expect_success();
+        // a second change in the same block replaces the checkpoint
+        builder.exec(deposit(100, 20)).commit().expect_success();
+
+        let withdraw_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "withdraw",
+            runtime_args! {
+                "pool_id" => 0u64,
+                "amount" => U256::from(300u64)
+            },
+        )
+        .with_block_time(30)
+        .build();
+
+        builder.exec(withdraw_req).commit().expect_success();
+
+        let set_emergency_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "set_emergency",
+            runtime_args! {
+                "pool_id" => 0u64,
+                "emergency" => true,
+            },
+        )
+        .with_block_time(40)
+        .build();
+
+        builder.exec(set_emergency_req).commit().expect_success();
+
+        let emergency_withdraw_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "emergency_withdraw",
+            runtime_args! {
+                "pool_id" => 0u64,
+            },
+        )
+        .with_block_time(40)
+        .build();
+
+        builder
+            .exec(emergency_withdraw_req)
+            .commit()
+            .expect_success();
+
+        let expected = vec![
+            (10u64, U256::from(1000u64)),
+            (20u64, U256::from(1600u64)),
+            (30u64, U256::from(1300u64)),
+            (40u64, U256::zero()),
+        ];
+
+        let user_checkpoints = get_checkpoints(
+            stake_contract,
+            KEY_NAME_USER_CHECKPOINTS,
+            &make_user_key_by_id(account, 0u64),
+            &builder,
+        );
+        assert_eq!(user_checkpoints, expected);
+
+        // only one staker, so the pool total follows the user
+        let pool_checkpoints = get_checkpoints(
+            stake_contract,
+            KEY_NAME_POOL_CHECKPOINTS,
+            &make_pool_key_id(0u64, stake_contract_package_key),
+            &builder,
+        );
+        assert_eq!(pool_checkpoints, expected);
+    }
+
+    #[test]
+    fn rate_pool_works() {
+        let (mut builder, stake_contract, stake_contract_package_key, erc20_token, account, supply) =
+            setup();
+
+        let TOTAL_REWARD = supply.checked_div(U256::from(2)).unwrap();
+        let reserve = U256::from(1000u64);
+        let amount_to_deposit = U256::from(1000u64);
+
+        // 10 tokens per second, funded for 100 seconds
+        let add_rate_pool = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "add_rate_pool",
+            runtime_args! {
+                "staking_token" => erc20_token.to_formatted_string(),
+                "reward_token" => erc20_token.to_formatted_string(),
+                "start_time" => 0u64,
+                "precision" => 18u64,
+                "reward_per_second" => U256::from(10u64),
+                "total_reward" => reserve
+            },
+        )
+        .build();
+
+        builder.exec(add_rate_pool).commit().expect_success();
+
+        let mut pool = get_pool_by_id(stake_contract, stake_contract_package_key, 1u64, &builder);
+        assert_eq!(pool.end_time, u64::MAX);
+        assert_eq!(pool.reward_per_second, U256::from(10u64));
+        assert_eq!(pool.total_reward, reserve);
+
+        let deposit_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "deposit",
+            runtime_args! {
+                "pool_id" => 1u64,
+                "amount" => amount_to_deposit,
+            },
+        )
+        .with_block_time(0)
+        .build();
+
+        builder.exec(deposit_req).commit().expect_success();
+
+        // the reserve runs out at 100, emission stops there
+        let update_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "mass_update_pools",
+            runtime_args! {
+                "pool_ids" => vec![1u64],
+            },
+        )
+        .with_block_time(150)
+        .build();
+
+        builder.exec(update_req).commit().expect_success();
+
+        pool = get_pool_by_id(stake_contract, stake_contract_package_key, 1u64, &builder);
+        assert_eq!(pool.emitted_reward, reserve);
+
+        // anyone can top up, emission resumes from now
+        let top_up_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "top_up_pool",
+            runtime_args! {
+                "pool_id" => 1u64,
+                "amount" => U256::from(500u64),
+            },
+        )
+        .with_block_time(200)
+        .build();
+
+        builder.exec(top_up_req).commit().expect_success();
+
+        pool = get_pool_by_id(stake_contract, stake_contract_package_key, 1u64, &builder);
+        assert_eq!(pool.total_reward, reserve + U256::from(500u64));
+        assert_eq!(pool.emitted_reward, reserve);
+
+        // 20 more seconds at 10 per second
+        let withdraw_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "withdraw",
+            runtime_args! {
+                "pool_id" => 1u64,
+                "amount" => amount_to_deposit
+            },
+        )
+        .with_block_time(220)
+        .build();
+
+        builder.exec(withdraw_req).commit().expect_success();
+
+        pool = get_pool_by_id(stake_contract, stake_contract_package_key, 1u64, &builder);
+        assert_eq!(pool.emitted_reward, reserve + U256::from(200u64));
+
+        let balance = get_token_balance(&account, erc20_token, &builder);
+        assert_eq!(
+            balance,
+            supply - TOTAL_REWARD - reserve - U256::from(500u64) + reserve + U256::from(200u64)
+        );
+    }
+
+    #[test]
+    fn withdraw_more_than_stake_fails() {
+        let (mut builder, stake_contract, _, _, _, _) = setup();
+
+        let deposit_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "deposit",
+            runtime_args! {
+                "pool_id" => 0u64,
+                "amount" => U256::from(1000u64),
+            },
+        )
+        .with_block_time(0)
+        .build();
+
+        builder.exec(deposit_req).commit().expect_success();
+
+        let withdraw_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "withdraw",
+            runtime_args! {
+                "pool_id" => 0u64,
+                "amount" => U256::from(1001u64),
+            },
+        )
+        .with_block_time(10)
+        .build();
+
+        builder.exec(withdraw_req).commit().expect_failure();
+
+        // reverts with Error::InsufficientStake, not an arithmetic trap
+        match builder.get_error() {
+            Some(engine_state::Error::Exec(execution::Error::Revert(ApiError::User(code)))) => {
+                assert_eq!(code, u16::MAX - 6)
+            }
+            error => panic!("unexpected error {:?}", error),
+        }
+    }
+
+    #[test]
+    fn reentrancy_lock_is_released() {
+        let (mut builder, stake_contract, _, _, _, _) = setup();
+
+        let deposit = |block_time: u64| {
+            ExecuteRequestBuilder::contract_call_by_hash(
+                *DEFAULT_ACCOUNT_ADDR,
+                stake_contract,
+                "deposit",
+                runtime_args! {
+                    "pool_id" => 0u64,
+                    "amount" => U256::from(1000u64),
+                },
+            )
+            .with_block_time(block_time)
+            .build()
+        };
+
+        builder.exec(deposit(0)).commit().expect_success();
+
+        assert!(!is_reentrancy_locked(stake_contract, &builder));
+
+        // a second call can take the lock again
+        builder.exec(deposit(10)).commit().expect_success();
+
+        assert!(!is_reentrancy_locked(stake_contract, &builder));
+    }
+
+    #[test]
+    fn cooldown_withdraw_works() {
+        let (mut builder, stake_contract, stake_contract_package_key, erc20_token, account, supply) =
+            setup();
+
+        let TOTAL_REWARD = supply.checked_div(U256::from(2)).unwrap();
+        let amount_to_deposit = U256::from(1000u64);
+        let amount_to_unbond = U256::from(400u64);
+
+        let set_cooldown = |cooldown: u64| {
+            ExecuteRequestBuilder::contract_call_by_hash(
+                *DEFAULT_ACCOUNT_ADDR,
+                stake_contract,
+                "set_cooldown",
+                runtime_args! {
+                    "pool_id" => 0u64,
+                    "cooldown" => cooldown,
+                },
+            )
+            .build()
+        };
+
+        // at most 30 days
+        builder
+            .exec(set_cooldown(2_592_001))
+            .commit()
+            .expect_failure();
+
+        builder.exec(set_cooldown(50)).commit().expect_success();
+
+        let deposit_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "deposit",
+            runtime_args! {
+                "pool_id" => 0u64,
+                "amount" => amount_to_deposit,
+            },
+        )
+        .with_block_time(0)
+        .build();
+
+        builder.exec(deposit_req).commit().expect_success();
+
+        // stakers keep the cooldown they entered under
+        builder.exec(set_cooldown(0)).commit().expect_failure();
+
+        // instant withdrawals are closed for cooldown pools
+        let withdraw_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "withdraw",
+            runtime_args! {
+                "pool_id" => 0u64,
+                "amount" => amount_to_unbond
+            },
+        )
+        .with_block_time(10)
+        .build();
+
+        builder.exec(withdraw_req).commit().expect_failure();
+
+        let request_withdraw_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "request_withdraw",
+            runtime_args! {
+                "pool_id" => 0u64,
+                "amount" => amount_to_unbond
+            },
+        )
+        .with_block_time(10)
+        .build();
+
+        builder.exec(request_withdraw_req).commit().expect_success();
+
+        // unbonded stake stops earning, rewards so far are paid
+        let pool = get_pool(stake_contract, stake_contract_package_key, &builder);
+        assert_eq!(pool.total_staked, amount_to_deposit - amount_to_unbond);
+
+        let mut balance = get_token_balance(&account, erc20_token, &builder);
+        assert_eq!(
+            balance,
+            supply - TOTAL_REWARD - amount_to_deposit + TOTAL_REWARD / 10
+        );
+
+        let complete_withdraw = |block_time: u64| {
+            ExecuteRequestBuilder::contract_call_by_hash(
+                *DEFAULT_ACCOUNT_ADDR,
+                stake_contract,
+                "complete_withdraw",
+                runtime_args! {
+                    "pool_id" => 0u64,
+                },
+            )
+            .with_block_time(block_time)
+            .build()
+        };
+
+        // still cooling down
+        builder
+            .exec(complete_withdraw(30))
+            .commit()
+            .expect_failure();
+
+        builder
+            .exec(complete_withdraw(60))
+            .commit()
+            .expect_success();
+
+        balance = get_token_balance(&account, erc20_token, &builder);
+        assert_eq!(
+            balance,
+            supply - TOTAL_REWARD - amount_to_deposit + amount_to_unbond + TOTAL_REWARD / 10
+        );
+
+        // nothing left to release
+        builder
+            .exec(complete_withdraw(70))
+            .commit()
+            .expect_failure();
+    }
+
+    #[test]
+    fn emergency_mode_works() {
+        let (mut builder, stake_contract, stake_contract_package_key, erc20_token, account, supply) =
+            setup();
+
+        let TOTAL_REWARD = supply.checked_div(U256::from(2)).unwrap();
+        let amount_to_deposit = U256::from(1000u64);
+
+        let call = |entry_point: &str, args: RuntimeArgs, block_time: u64| {
+            ExecuteRequestBuilder::contract_call_by_hash(
+                *DEFAULT_ACCOUNT_ADDR,
+                stake_contract,
+                entry_point,
+                args,
+            )
+            .with_block_time(block_time)
+            .build()
+        };
+
+        let expect_error =
+            |builder: &InMemoryWasmTestBuilder, expected: u16| match builder.get_error() {
+                Some(engine_state::Error::Exec(execution::Error::Revert(ApiError::User(code)))) => {
+                    assert_eq!(code, expected)
+                }
+                error => panic!("unexpected error {:?}", error),
+            };
+
+        builder
+            .exec(call(
+                "deposit",
+                runtime_args! { "pool_id" => 0u64, "amount" => amount_to_deposit },
+                0,
+            ))
+            .commit()
+            .expect_success();
+
+        // closed while the pool runs normally
+        builder
+            .exec(call(
+                "emergency_withdraw",
+                runtime_args! { "pool_id" => 0u64 },
+                20,
+            ))
+            .commit()
+            .expect_failure();
+        expect_error(&builder, u16::MAX - 8);
+
+        builder
+            .exec(call(
+                "set_emergency",
+                runtime_args! { "pool_id" => 0u64, "emergency" => true },
+                50,
+            ))
+            .commit()
+            .expect_success();
+
+        let pool = get_pool(stake_contract, stake_contract_package_key, &builder);
+        assert!(pool.emergency);
+        assert_eq!(pool.emitted_reward, TOTAL_REWARD / 2);
+
+        // normal withdrawals and harvests are disabled
+        builder
+            .exec(call(
+                "withdraw",
+                runtime_args! { "pool_id" => 0u64, "amount" => amount_to_deposit },
+                60,
+            ))
+            .commit()
+            .expect_failure();
+        expect_error(&builder, u16::MAX - 7);
+
+        builder
+            .exec(call(
+                "harvest_many",
+                runtime_args! { "pool_ids" => vec![0u64] },
+                60,
+            ))
+            .commit()
+            .expect_failure();
+        expect_error(&builder, u16::MAX - 7);
+
+        builder
+            .exec(call(
+                "emergency_withdraw",
+                runtime_args! { "pool_id" => 0u64 },
+                60,
+            ))
+            .commit()
+            .expect_success();
+
+        // principal back, no rewards
+        let mut balance = get_token_balance(&account, erc20_token, &builder);
+        assert_eq!(balance, supply - TOTAL_REWARD);
+
+        // the forfeited rewards go back to the owner
+        let pool = get_pool(stake_contract, stake_contract_package_key, &builder);
+        assert_eq!(pool.unallocated_reward, TOTAL_REWARD / 2);
+
+        // rewards stay frozen past the end of the pool
+        builder
+            .exec(call(
+                "mass_update_pools",
+                runtime_args! { "pool_ids" => vec![0u64] },
+                120,
+            ))
+            .commit()
+            .expect_success();
+
+        let pool = get_pool(stake_contract, stake_contract_package_key, &builder);
+        assert_eq!(pool.emitted_reward, TOTAL_REWARD / 2);
+
+        // and the owner recovers them
+        builder
+            .exec(call(
+                "stop_reward",
+                runtime_args! { "pool_id" => 0u64 },
+                120,
+            ))
+            .commit()
+            .expect_success();
+
+        let pool = get_pool(stake_contract, stake_contract_package_key, &builder);
+        assert_eq!(pool.end_time, 100u64);
+
+        // the rewards nobody emitted and the forfeited ones
+        balance = get_token_balance(&account, erc20_token, &builder);
+        assert_eq!(balance, supply);
+    }
+
+    #[test]
+    fn emergency_window_rewards_go_to_owner() {
+        let (mut builder, stake_contract, stake_contract_package_key, _, _, supply) = setup();
+
+        let TOTAL_REWARD = supply.checked_div(U256::from(2)).unwrap();
+
+        let call = |entry_point: &str, args: RuntimeArgs, block_time: u64| {
+            ExecuteRequestBuilder::contract_call_by_hash(
+                *DEFAULT_ACCOUNT_ADDR,
+                stake_contract,
+                entry_point,
+                args,
+            )
+            .with_block_time(block_time)
+            .build()
+        };
+
+        builder
+            .exec(call(
+                "deposit",
+                runtime_args! { "pool_id" => 0u64, "amount" => U256::from(1000u64) },
+                0,
+            ))
+            .commit()
+            .expect_success();
+
+        builder
+            .exec(call(
+                "set_emergency",
+                runtime_args! { "pool_id" => 0u64, "emergency" => true },
+                20,
+            ))
+            .commit()
+            .expect_success();
+
+        builder
+            .exec(call(
+                "set_emergency",
+                runtime_args! { "pool_id" => 0u64, "emergency" => false },
+                40,
+            ))
+            .commit()
+            .expect_success();
+
+        // the 20 seconds in emergency mode are emitted to the owner
+        let pool = get_pool(stake_contract, stake_contract_package_key, &builder);
+        assert_eq!(pool.emitted_reward, TOTAL_REWARD * 2 / 5);
+        assert_eq!(pool.unallocated_reward, TOTAL_REWARD / 5);
+
+        // and can be reclaimed after the pool ended
+        builder
+            .exec(call(
+                "reclaim_unallocated",
+                runtime_args! { "pool_id" => 0u64 },
+                120,
+            ))
+            .commit()
+            .expect_success();
+
+        let pool = get_pool(stake_contract, stake_contract_package_key, &builder);
+        assert_eq!(pool.emitted_reward, TOTAL_REWARD);
+        assert_eq!(pool.unallocated_reward, U256::zero());
+    }
+
+    #[test]
+    fn pool_factory_works() {
+        let (mut builder, stake_contract, _, erc20_token, _, _) = setup();
+
+        let total_reward = U256::from(1_000_000u64);
+
+        // setup() approved the factory, which funds the new pool from the caller
+        let create_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "create_pool_contract",
+            runtime_args! {
+                "staking_token" => erc20_token.to_formatted_string(),
+                "reward_token" => erc20_token.to_formatted_string(),
+                "start_time" => 0u64,
+                "end_time" => 100u64,
+                "precision" => 18u64,
+                "total_reward" => total_reward,
+                "name" => String::from("SWAP farm"),
+                "cooldown" => 10u64
+            },
+        )
+        .build();
+
+        builder.exec(create_req).commit().expect_success();
+
+        let count: u64 = builder.get_value(stake_contract, POOL_CONTRACT_COUNT_KEY);
+        assert_eq!(count, 1u64);
+
+        let (pool_contract, pool_package) = get_pool_contract(stake_contract, 0u64, &builder);
+
+        let initialized: bool = builder.get_value(pool_contract, INIT_KEY);
+        assert!(initialized);
+
+        // administered by the factory's admin
+        let admin: AccountHash = builder.get_value(pool_contract, ADMIN_KEY);
+        assert_eq!(admin, *DEFAULT_ACCOUNT_ADDR);
+
+        // its pool is added and funded, owned by the caller
+        let pool = get_pool(pool_contract, pool_package, &builder);
+        assert_eq!(pool.total_reward, total_reward);
+        assert_eq!(pool.owner, *DEFAULT_ACCOUNT_ADDR);
+
+        // optional add_pool args are passed on
+        assert_eq!(pool.cooldown, 10u64);
+
+        let metadata = get_pool_metadata(pool_contract, 0u64, &builder);
+        assert_eq!(metadata["name"], "SWAP farm");
+
+        // the pool contract holds one pool only
+        let add_pool = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            pool_contract,
+            "add_pool",
+            runtime_args! {
+                "staking_token" => erc20_token.to_formatted_string(),
+                "reward_token" => erc20_token.to_formatted_string(),
+                "start_time" => 0u64,
+                "end_time" => 100u64,
+                "precision" => 18u64,
+                "total_reward" => total_reward
+            },
+        )
+        .build();
+
+        builder.exec(add_pool).commit().expect_failure();
+
+        // reward_per_second makes it a rate pool
+        let create_rate_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "create_pool_contract",
+            runtime_args! {
+                "staking_token" => erc20_token.to_formatted_string(),
+                "reward_token" => erc20_token.to_formatted_string(),
+                "start_time" => 0u64,
+                "precision" => 18u64,
+                "reward_per_second" => U256::from(1_000u64),
+                "total_reward" => total_reward
+            },
+        )
+        .build();
+
+        builder.exec(create_rate_req).commit().expect_success();
+
+        let count: u64 = builder.get_value(stake_contract, POOL_CONTRACT_COUNT_KEY);
+        assert_eq!(count, 2u64);
+
+        let (rate_contract, rate_package) = get_pool_contract(stake_contract, 1u64, &builder);
+
+        let pool = get_pool(rate_contract, rate_package, &builder);
+        assert_eq!(pool.reward_per_second, U256::from(1_000u64));
+        assert_eq!(pool.end_time, u64::MAX);
+        assert_eq!(pool.total_reward, total_reward);
+    }
+
+    #[test]
+    fn fee_on_transfer_deposit_works() {
+        let (mut builder, stake_contract, stake_contract_package_key, erc20_token, account, supply) =
+            setup();
+
+        // burns 1% of every transfer
+        let install_fee_token = ExecuteRequestBuilder::standard(
+            *DEFAULT_ACCOUNT_ADDR,
+            FEE_TOKEN_WASM,
+            runtime_args! {
+                "total_supply" => supply
+            },
+        )
+        .build();
+
+        builder.exec(install_fee_token).commit().expect_success();
+
+        let fee_token = builder
+            .get_account(*DEFAULT_ACCOUNT_ADDR)
+            .expect("should have account")
+            .named_keys()
+            .get(FEE_TOKEN_CONTRACT_KEY)
+            .and_then(|key| key.into_hash())
+            .map(ContractHash::new)
+            .expect("should have contract hash");
+
+        let add_pool = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "add_pool",
+            runtime_args! {
+                "staking_token" => fee_token.to_formatted_string(),
+                "reward_token" => erc20_token.to_formatted_string(),
+                "start_time" => 0u64,
+                "end_time" => 100u64,
+                "precision" => 18u64,
+                "total_reward" => U256::from(1_000_000u64)
+            },
+        )
+        .build();
+
+        builder.exec(add_pool).commit().expect_success();
+
+        let approve = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            fee_token,
+            "approve",
+            runtime_args! {
+                "spender" => Key::from(stake_contract_package_key),
+                "amount" => supply
+            },
+        )
+        .build();
+
+        builder.exec(approve).commit().expect_success();
+
+        let amount_to_deposit = U256::from(100_000u64);
+        let received = U256::from(99_000u64);
+
+        let deposit_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "deposit",
+            runtime_args! {
+                "pool_id" => 1u64,
+                "amount" => amount_to_deposit,
+            },
+        )
+        .with_block_time(0)
+        .build();
+
+        builder.exec(deposit_req).commit().expect_success();
+
+        // only what arrived after the fee is staked
+        let user_info = get_user_info_by_id(stake_contract, &account, 1, &builder);
+        assert_eq!(user_info.amount, received);
+
+        let pool = get_pool_by_id(stake_contract, stake_contract_package_key, 1, &builder);
+        assert_eq!(pool.total_staked, received);
+    }
+
+    #[test]
+    fn native_reward_pool_works() {
+        let (mut builder, stake_contract, stake_contract_package_key, erc20_token, account, supply) =
+            setup();
+
+        let TOTAL_REWARD = U256::from(1_000_000_000_000_u64);
+        let amount_to_deposit = U256::from(100_000_000_000_u64);
+
+        // call add_native_pool() through the session portal, which funds a temporary purse
+        let add_native_pool = ExecuteRequestBuilder::standard(
+            *DEFAULT_ACCOUNT_ADDR,
+            STAKE_PORTAL_WASM,
+            runtime_args! {
+                "entry_point" => 4u8,
+                "contract_hash" => stake_contract.to_formatted_string(),
+                "staking_token" => erc20_token.to_formatted_string(),
+                "start_time" => 0u64,
+                "end_time" => 100u64,
+                "precision" => 18u64,
+                "total_reward" => TOTAL_REWARD
+            },
+        )
+        .with_block_time(0)
+        .build();
+
+        builder.exec(add_native_pool).commit().expect_success();
+
+        // index is now 2
+        let index: u64 = builder.get_value(stake_contract, INDEX_KEY);
+        assert_eq!(index, 2_u64);
+
+        let mut pool = get_pool_by_id(stake_contract, stake_contract_package_key, 1, &builder);
+
+        // verify values of input pool
+        assert!(pool.native_reward);
+        assert_eq!(pool.staking_token, erc20_token);
+        assert_eq!(pool.total_reward, TOTAL_REWARD);
+        assert_eq!(pool.owner, *DEFAULT_ACCOUNT_ADDR);
+
+        // rewards are held in the pool's own purse
+        let mut purse_balance = get_reward_purse_balance(stake_contract, 1, &builder);
+        assert_eq!(purse_balance, U512::from(1_000_000_000_000_u64));
+
+        let balance_pre = get_token_balance(&account, erc20_token, &builder);
+
+        // call deposit()
+        let deposit_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "deposit",
+            runtime_args! {
+                "pool_id" => 1u64,
+                "amount" => amount_to_deposit,
+            },
+        )
+        .with_block_time(0)
+        .build();
+
+        builder.exec(deposit_req).commit().expect_success();
+
+        pool = get_pool_by_id(stake_contract, stake_contract_package_key, 1, &builder);
+        assert_eq!(pool.total_staked, amount_to_deposit);
+
+        // call withdraw() after fully finished
+        let withdraw_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "withdraw",
+            runtime_args! {
+                "pool_id" => 1u64,
+                "amount" => amount_to_deposit
+            },
+        )
+        .with_block_time(101)
+        .build();
+
+        builder.exec(withdraw_req).commit().expect_success();
+
+        pool = get_pool_by_id(stake_contract, stake_contract_package_key, 1, &builder);
+        assert_eq!(pool.total_staked, U256::zero());
+
+        // staking tokens returned, rewards paid in CSPR
+        let balance = get_token_balance(&account, erc20_token, &builder);
+        assert_eq!(balance, balance_pre);
+
+        // allow 1e-6 of dust
+        purse_balance = get_reward_purse_balance(stake_contract, 1, &builder);
+        assert!(purse_balance <= U512::from(1_000));
+    }
+
+    #[test]
+    fn reclaim_unallocated_works() {
+        let (mut builder, stake_contract, stake_contract_package_key, erc20_token, account, supply) =
+            setup();
+
+        let TOTAL_REWARD = supply.checked_div(U256::from(2)).unwrap();
+        let amount_to_deposit = U256::from(100_000_000_000_u64);
+
+        // first deposit only comes halfway through the pool
+        let deposit_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "deposit",
+            runtime_args! {
+                "pool_id" => 0u64,
+                "amount" => amount_to_deposit,
+            },
+        )
+        .with_block_time(50)
+        .build();
+
+        builder.exec(deposit_req).commit().expect_success();
+
+        let mut pool = get_pool(stake_contract, stake_contract_package_key, &builder);
+
+        // rewards of the first half went to nobody
+        assert_eq!(pool.unallocated_reward, TOTAL_REWARD / U256::from(2));
+        assert_eq!(pool.acc_token_per_share, U256::zero());
+
+        let balance_pre = get_token_balance(&account, erc20_token, &builder);
+
+        // only the pool owner can reclaim
+        let (bob, _) = AccountHash::from_bytes(&BOB).unwrap();
+        let id: Option<u64> = None;
+        let fund_bob = ExecuteRequestBuilder::transfer(
+            *DEFAULT_ACCOUNT_ADDR,
+            runtime_args! {
+                mint::ARG_TARGET => bob,
+                mint::ARG_AMOUNT => MINIMUM_ACCOUNT_CREATION_BALANCE,
+                mint::ARG_ID => id,
+            },
+        )
+        .build();
+
+        builder.exec(fund_bob).commit().expect_success();
+
+        let reclaim_not_owner = ExecuteRequestBuilder::contract_call_by_hash(
+            bob,
+            stake_contract,
+            "reclaim_unallocated",
+            runtime_args! {
+                "pool_id" => 0u64,
+            },
+        )
+        .with_block_time(60)
+        .build();
+
+        builder.exec(reclaim_not_owner).commit().expect_failure();
+
+        // call reclaim_unallocated()
+        let reclaim_req = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "reclaim_unallocated",
+            runtime_args! {
+                "pool_id" => 0u64,
+            },
+        )
+        .with_block_time(60)
+        .build();
+
+        builder.exec(reclaim_req).commit().expect_success();
+
+        pool = get_pool(stake_contract, stake_contract_package_key, &builder);
+        assert_eq!(pool.unallocated_reward, U256::zero());
+
+        let balance = get_token_balance(&account, erc20_token, &builder);
+        assert_eq!(balance, balance_pre + TOTAL_REWARD / U256::from(2));
+
+        // nothing left to reclaim
+        let reclaim_again = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "reclaim_unallocated",
+            runtime_args! {
+                "pool_id" => 0u64,
+            },
+        )
+        .with_block_time(70)
+        .build();
+
+        builder.exec(reclaim_again).commit().expect_failure();
     }
 
     #[test]
-    fn set_admin_works() {
+    fn emission_epochs_works() {
         let (
             mut builder,
             stake_contract,
@@ -1394,86 +2867,242 @@ mod tests {
             erc20_tokens,
             accounts,
             supply,
-        ) = setup_multiple_users();
+        ) = setup_multiple_users_different_tokens();
 
-        let mut admin: AccountHash = builder.get_value(stake_contract, ADMIN_KEY);
+        let TOTAL_REWARD = supply.checked_div(U256::from(2)).unwrap();
+        let first_epoch = TOTAL_REWARD * U256::from(3) / U256::from(4);
+        let epochs: Vec<(u64, U256)> = vec![(150, first_epoch), (200, TOTAL_REWARD - first_epoch)];
 
-        assert_eq!(admin, accounts[0].account_hash());
+        // only the pool owner can set the curve
+        let set_curve_not_owner = ExecuteRequestBuilder::contract_call_by_hash(
+            accounts[1].account_hash(),
+            stake_contract,
+            "set_emission_curve",
+            runtime_args! {
+                "pool_id" => 0u64,
+                "emission_curve" => 1u8,
+                "epochs" => epochs.clone(),
+                "halving_period" => 0u64,
+            },
+        )
+        .with_block_time(0)
+        .build();
 
-        // call set_admin()
-        let mut set_admin_req = ExecuteRequestBuilder::contract_call_by_hash(
+        builder.exec(set_curve_not_owner).commit().expect_failure();
+
+        // epochs must add up to total_reward
+        let set_curve_bad_sum = ExecuteRequestBuilder::contract_call_by_hash(
             *DEFAULT_ACCOUNT_ADDR,
             stake_contract,
-            "set_admin",
+            "set_emission_curve",
             runtime_args! {
-                "new_admin" => accounts[1].account_hash(),
+                "pool_id" => 0u64,
+                "emission_curve" => 1u8,
+                "epochs" => vec![(150u64, first_epoch), (200u64, first_epoch)],
+                "halving_period" => 0u64,
             },
         )
-        .with_block_time(20)
+        .with_block_time(0)
         .build();
 
-        builder.exec(set_admin_req).commit().expect_success();
+        builder.exec(set_curve_bad_sum).commit().expect_failure();
 
-        admin = builder.get_value(stake_contract, ADMIN_KEY);
+        // front-load 3/4 of the rewards into the first half
+        let set_curve = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            stake_contract,
+            "set_emission_curve",
+            runtime_args! {
+                "pool_id" => 0u64,
+                "emission_curve" => 1u8,
+                "epochs" => epochs.clone(),
+                "halving_period" => 0u64,
+            },
+        )
+        .with_block_time(0)
+        .build();
 
-        // new admin set successfully
-        assert_eq!(admin, accounts[1].account_hash());
+        builder.exec(set_curve).commit().expect_success();
 
-        // old admin no longer can call set_admin
-        set_admin_req = ExecuteRequestBuilder::contract_call_by_hash(
+        let amount_to_deposit = U256::from(100_000_000_000_u64);
+
+        // call deposit()
+        let deposit_req = ExecuteRequestBuilder::contract_call_by_hash(
+            accounts[2].account_hash(),
+            stake_contract,
+            "deposit",
+            runtime_args! {
+                "pool_id" => 0u64,
+                "amount" => amount_to_deposit,
+            },
+        )
+        .with_block_time(100)
+        .build();
+
+        builder.exec(deposit_req).commit().expect_success();
+
+        // can't change the curve once the pool started
+        let set_curve_late = ExecuteRequestBuilder::contract_call_by_hash(
             *DEFAULT_ACCOUNT_ADDR,
             stake_contract,
-            "set_admin",
+            "set_emission_curve",
             runtime_args! {
-                "new_admin" => accounts[3].account_hash(),
+                "pool_id" => 0u64,
+                "emission_curve" => 2u8,
+                "epochs" => Vec::<(u64, U256)>::new(),
+                "halving_period" => 0u64,
             },
         )
-        .with_block_time(21)
+        .with_block_time(120)
         .build();
 
-        builder.exec(set_admin_req).commit().expect_failure();
+        builder.exec(set_curve_late).commit().expect_failure();
 
-        // new admin can now call set_admin
-        set_admin_req = ExecuteRequestBuilder::contract_call_by_hash(
-            accounts[1].account_hash(),
+        // call withdraw() at the end of the first epoch
+        let withdraw_req = ExecuteRequestBuilder::contract_call_by_hash(
+            accounts[2].account_hash(),
             stake_contract,
-            "set_admin",
+            "withdraw",
             runtime_args! {
-                "new_admin" => accounts[3].account_hash(),
+                "pool_id" => 0u64,
+                "amount" => amount_to_deposit
             },
         )
-        .with_block_time(22)
+        .with_block_time(150)
         .build();
 
-        builder.exec(set_admin_req).commit().expect_success();
+        builder.exec(withdraw_req).commit().expect_success();
 
-        admin = builder.get_value(stake_contract, ADMIN_KEY);
+        let pool = get_pool(stake_contract, stake_contract_package_key, &builder);
+        assert_eq!(pool.total_staked, U256::zero());
 
-        // new admin set successfully
-        assert_eq!(admin, accounts[3].account_hash());
+        // staking tokens returned
+        let balance = get_token_balance(&accounts[2], erc20_tokens[0], &builder);
+        assert_eq!(balance, amount_to_deposit);
+
+        // whole first epoch earned, allow 1e-6 of dust
+        let reward_balance = get_token_balance(&accounts[2], erc20_tokens[1], &builder);
+        assert!(reward_balance <= first_epoch);
+        assert!(reward_balance >= first_epoch - U256::from(1_000));
     }
 
     #[test]
-    fn withdraw_works() {
+    fn stopped_emergency_pool_keeps_refund() {
+        let (mut builder, stake_contract, stake_contract_package_key, erc20_token, _, _) = setup();
+
+        let call = |entry_point: &str, args: RuntimeArgs, block_time: u64| {
+            ExecuteRequestBuilder::contract_call_by_hash(
+                *DEFAULT_ACCOUNT_ADDR,
+                stake_contract,
+                entry_point,
+                args,
+            )
+            .with_block_time(block_time)
+            .build()
+        };
+
+        builder
+            .exec(call(
+                "add_pool",
+                runtime_args! {
+                    "staking_token" => erc20_token.to_formatted_string(),
+                    "reward_token" => erc20_token.to_formatted_string(),
+                    "start_time" => 0u64,
+                    "end_time" => 100u64,
+                    "precision" => 18u64,
+                    "total_reward" => U256::from(1_000_000u64),
+                    "emission_curve" => 2u8
+                },
+                0,
+            ))
+            .commit()
+            .expect_success();
+
+        builder
+            .exec(call(
+                "deposit",
+                runtime_args! { "pool_id" => 1u64, "amount" => U256::from(1000u64) },
+                0,
+            ))
+            .commit()
+            .expect_success();
+
+        builder
+            .exec(call(
+                "set_emergency",
+                runtime_args! { "pool_id" => 1u64, "emergency" => true },
+                20,
+            ))
+            .commit()
+            .expect_success();
+
+        // stopped before its end, the rest of the budget is refunded
+        builder
+            .exec(call("stop_reward", runtime_args! { "pool_id" => 1u64 }, 40))
+            .commit()
+            .expect_success();
+
+        builder
+            .exec(call(
+                "set_emergency",
+                runtime_args! { "pool_id" => 1u64, "emergency" => false },
+                60,
+            ))
+            .commit()
+            .expect_success();
+
+        // the refunded window isn't emitted a second time
+        let pool = get_pool_by_id(stake_contract, stake_contract_package_key, 1, &builder);
+        assert_eq!(pool.emitted_reward, pool.total_reward);
+        assert_eq!(pool.unallocated_reward, U256::zero());
+
+        builder
+            .exec(call(
+                "reclaim_unallocated",
+                runtime_args! { "pool_id" => 1u64 },
+                60,
+            ))
+            .commit()
+            .expect_failure();
+    }
+
+    #[test]
+    fn add_pool_emission_curve_works() {
         let (mut builder, stake_contract, stake_contract_package_key, erc20_token, account, supply) =
             setup();
 
-        let TOTAL_REWARD = supply.checked_div(U256::from(2)).unwrap();
-        // index is now 1
-        let index: u64 = builder.get_value(stake_contract, INDEX_KEY);
-        assert_eq!(index, 1_u64);
+        let TOTAL_REWARD = U256::from(100_000_000_000_u64);
+        let amount_to_deposit = U256::from(100_000_000_000_u64);
 
-        let mut pool = get_pool(stake_contract, stake_contract_package_key, &builder);
-        // verify values of input pool
-        assert_eq!(pool.start_time, 0u64);
-        assert_eq!(pool.end_time, 100u64);
-        assert_eq!(pool.total_reward, TOTAL_REWARD);
-        assert_eq!(pool.reward_token, pool.staking_token);
-        assert_eq!(pool.owner, *DEFAULT_ACCOUNT_ADDR);
+        let add_pool = |halving_period: u64, emission_curve: u8| {
+            ExecuteRequestBuilder::contract_call_by_hash(
+                *DEFAULT_ACCOUNT_ADDR,
+                stake_contract,
+                "add_pool",
+                runtime_args! {
+                    "staking_token" => erc20_token.to_formatted_string(),
+                    "reward_token" => erc20_token.to_formatted_string(),
+                    "start_time" => 0u64,
+                    "end_time" => 100u64,
+                    "precision" => 18u64,
+                    "total_reward" => TOTAL_REWARD,
+                    "emission_curve" => emission_curve,
+                    "halving_period" => halving_period
+                },
+            )
+            .with_block_time(0)
+            .build()
+        };
 
-        let mut balance = get_token_balance(&account, erc20_token, &builder);
+        // the curve is validated when the pool is created
+        builder.exec(add_pool(0, 3)).commit().expect_failure();
 
-        assert_eq!(balance, supply - TOTAL_REWARD);
+        // a pool starting right away gets its curve in the same call
+        builder.exec(add_pool(0, 2)).commit().expect_success();
+
+        // index is now 2
+        let index: u64 = builder.get_value(stake_contract, INDEX_KEY);
+        assert_eq!(index, 2_u64);
 
         // call deposit()
         let deposit_req = ExecuteRequestBuilder::contract_call_by_hash(
@@ -1481,8 +3110,8 @@ mod tests {
             stake_contract,
             "deposit",
             runtime_args! {
-                "pool_id" => 0u64,
-                "amount" => TOTAL_REWARD,
+                "pool_id" => 1u64,
+                "amount" => amount_to_deposit,
             },
         )
         .with_block_time(0)
@@ -1490,40 +3119,27 @@ mod tests {
 
         builder.exec(deposit_req).commit().expect_success();
 
-        pool = get_pool(stake_contract, stake_contract_package_key, &builder);
-        // verify values of pool after deposit
-        assert_eq!(pool.total_staked, TOTAL_REWARD);
-
-        // check balance is deducted
-        balance = get_token_balance(&account, erc20_token, &builder);
-
-        assert_eq!(balance, supply - TOTAL_REWARD - TOTAL_REWARD);
-
-        // call withdraw() after fully finished
+        // call withdraw() halfway through
         let withdraw_req = ExecuteRequestBuilder::contract_call_by_hash(
             *DEFAULT_ACCOUNT_ADDR,
             stake_contract,
             "withdraw",
             runtime_args! {
-                "pool_id" => 0u64,
-                "amount" => TOTAL_REWARD
+                "pool_id" => 1u64,
+                "amount" => amount_to_deposit
             },
         )
-        .with_block_time(101)
+        .with_block_time(50)
         .build();
 
         builder.exec(withdraw_req).commit().expect_success();
 
-        pool = get_pool(stake_contract, stake_contract_package_key, &builder);
-
-        // verify all tokens withdrawn
-        assert_eq!(pool.total_staked, U256::zero());
-
-        // check balance is added
-        balance = get_token_balance(&account, erc20_token, &builder);
-
-        // Should have all tokens back
-        assert_eq!(balance, supply);
+        // linear decay emits 3/4 of the rewards in the first half
+        let pool = get_pool_by_id(stake_contract, stake_contract_package_key, 1, &builder);
+        assert_eq!(
+            pool.emitted_reward,
+            TOTAL_REWARD * U256::from(3) / U256::from(4)
+        );
     }
 
     #[test]
@@ -2805,6 +4421,15 @@ mod tests {
         stake_contract: ContractHash,
         stake_contract_package_key: ContractPackageHash,
         builder: &WasmTestBuilder<InMemoryGlobalState>,
+    ) -> StakePool {
+        get_pool_by_id(stake_contract, stake_contract_package_key, 0_u64, builder)
+    }
+
+    fn get_pool_by_id(
+        stake_contract: ContractHash,
+        stake_contract_package_key: ContractPackageHash,
+        pool_id: u64,
+        builder: &WasmTestBuilder<InMemoryGlobalState>,
     ) -> StakePool {
         let contract_keys: NamedKeys = builder
             .to_owned()
@@ -2814,7 +4439,7 @@ mod tests {
 
         let seed_uref: Key = *contract_keys.get(KEY_NAME_DIC_STAKE_INFOS).unwrap();
 
-        let pool_key = make_pool_key_id(0_u64, stake_contract_package_key);
+        let pool_key = make_pool_key_id(pool_id, stake_contract_package_key);
 
         let binding: StoredValue = builder
             .query_dictionary_item(None, *seed_uref.as_uref().unwrap(), &pool_key)
@@ -2833,6 +4458,15 @@ mod tests {
         stake_contract: ContractHash,
         account: &Account,
         builder: &WasmTestBuilder<InMemoryGlobalState>,
+    ) -> UserInfo {
+        get_user_info_by_id(stake_contract, account, 0_u64, builder)
+    }
+
+    fn get_user_info_by_id(
+        stake_contract: ContractHash,
+        account: &Account,
+        pool_id: u64,
+        builder: &WasmTestBuilder<InMemoryGlobalState>,
     ) -> UserInfo {
         let contract_keys: NamedKeys = builder
             .to_owned()
@@ -2842,7 +4476,7 @@ mod tests {
 
         let seed_uref: Key = *contract_keys.get(KEY_NAME_USER_INFOS).unwrap();
 
-        let user_key = make_user_key_by_id(account.to_owned(), 0_u64);
+        let user_key = make_user_key_by_id(account.to_owned(), pool_id);
 
         let binding: StoredValue = builder
             .query_dictionary_item(None, *seed_uref.as_uref().unwrap(), &user_key)
@@ -2857,6 +4491,138 @@ mod tests {
         UserInfo::unpack(info_bytes.to_vec())
     }
 
+    // (timestamp, amount) checkpoints stored for key in dictionary,
+    // one entry per checkpoint under "<key>_<n>" plus "<key>_count"
+    fn get_checkpoints(
+        stake_contract: ContractHash,
+        dictionary: &str,
+        key: &str,
+        builder: &WasmTestBuilder<InMemoryGlobalState>,
+    ) -> Vec<(u64, U256)> {
+        let contract_keys: NamedKeys = builder
+            .to_owned()
+            .get_contract(stake_contract)
+            .unwrap()
+            .take_named_keys();
+
+        let seed_uref: Key = *contract_keys.get(dictionary).unwrap();
+
+        let count: u64 = builder
+            .query_dictionary_item(
+                None,
+                *seed_uref.as_uref().unwrap(),
+                &(key.to_string() + "_count"),
+            )
+            .expect("Doesn't exist")
+            .as_cl_value()
+            .unwrap()
+            .to_owned()
+            .into_t()
+            .unwrap();
+
+        (0..count)
+            .map(|index| {
+                let binding: StoredValue = builder
+                    .query_dictionary_item(
+                        None,
+                        *seed_uref.as_uref().unwrap(),
+                        &(key.to_string() + "_" + &index.to_string()),
+                    )
+                    .expect("Doesn't exist");
+
+                let g = binding.as_cl_value().unwrap().inner_bytes();
+
+                // cut off CLType bytes
+                let checkpoint = &g[4..g.len()];
+
+                (
+                    u64::from_le_bytes(checkpoint[0..8].try_into().unwrap()),
+                    U256::from_little_endian(&checkpoint[8..40]),
+                )
+            })
+            .collect()
+    }
+
+    // hashes of the pool contract at index in the factory's registry
+    fn get_pool_contract(
+        stake_contract: ContractHash,
+        index: u64,
+        builder: &WasmTestBuilder<InMemoryGlobalState>,
+    ) -> (ContractHash, ContractPackageHash) {
+        let contract_keys: NamedKeys = builder
+            .to_owned()
+            .get_contract(stake_contract)
+            .unwrap()
+            .take_named_keys();
+
+        let seed_uref: Key = *contract_keys.get(KEY_NAME_POOL_CONTRACTS).unwrap();
+
+        let binding: StoredValue = builder
+            .query_dictionary_item(None, *seed_uref.as_uref().unwrap(), &index.to_string())
+            .expect("Doesn't exist");
+
+        binding.as_cl_value().unwrap().to_owned().into_t().unwrap()
+    }
+
+    fn get_pool_metadata(
+        stake_contract: ContractHash,
+        pool_id: u64,
+        builder: &WasmTestBuilder<InMemoryGlobalState>,
+    ) -> BTreeMap<String, String> {
+        let contract_keys: NamedKeys = builder
+            .to_owned()
+            .get_contract(stake_contract)
+            .unwrap()
+            .take_named_keys();
+
+        let seed_uref: Key = *contract_keys.get(KEY_NAME_POOL_METADATA).unwrap();
+
+        let binding: StoredValue = builder
+            .query_dictionary_item(None, *seed_uref.as_uref().unwrap(), &pool_id.to_string())
+            .expect("Doesn't exist");
+
+        binding.as_cl_value().unwrap().to_owned().into_t().unwrap()
+    }
+
+    fn get_reward_purse_balance(
+        stake_contract: ContractHash,
+        pool_id: u64,
+        builder: &WasmTestBuilder<InMemoryGlobalState>,
+    ) -> U512 {
+        let contract_keys: NamedKeys = builder
+            .to_owned()
+            .get_contract(stake_contract)
+            .unwrap()
+            .take_named_keys();
+
+        let purse_key: Key = *contract_keys
+            .get(&(String::from(REWARD_PURSE_KEY) + &pool_id.to_string()))
+            .unwrap();
+
+        builder.get_purse_balance(*purse_key.as_uref().unwrap())
+    }
+
+    fn is_reentrancy_locked(
+        stake_contract: ContractHash,
+        builder: &WasmTestBuilder<InMemoryGlobalState>,
+    ) -> bool {
+        let contract_keys: NamedKeys = builder
+            .to_owned()
+            .get_contract(stake_contract)
+            .unwrap()
+            .take_named_keys();
+
+        let lock_key: Key = *contract_keys.get("reentrancy_lock").unwrap();
+
+        let lock: StoredValue = builder.query(None, lock_key, &[]).expect("Doesn't exist");
+
+        lock.as_cl_value()
+            .unwrap()
+            .to_owned()
+            .into_t::<bool>()
+            .unwrap()
+    }
+
     pub fn get_token_balance(
         account: &Account,
         token_hash: ContractHash,
@@ -2977,6 +4743,18 @@ mod tests {
         pub acc_token_per_share: U256,
         // 32 : 232
         pub total_staked: U256,
+        // 1  : 233
+        pub native_reward: bool,
+        // 32 : 265
+        pub unallocated_reward: U256,
+        // 32 : 297
+        pub emitted_reward: U256,
+        // 32 : 329
+        pub reward_per_second: U256,
+        // 8  : 337
+        pub cooldown: u64,
+        // 1  : 338
+        pub emergency: bool,
     }
 
     impl StakePool {
@@ -3048,6 +4826,38 @@ mod tests {
             for i in 200..232 {
                 res.push(total_staked_bytes[i - 200])
             }
+
+            res.push(self.native_reward as u8);
+
+            let mut unallocated_reward_bytes = [0_u8; 64];
+            self.unallocated_reward
+                .to_little_endian(&mut unallocated_reward_bytes[..32]);
+
+            for i in 233..265 {
+                res.push(unallocated_reward_bytes[i - 233])
+            }
+
+            let mut emitted_reward_bytes = [0_u8; 64];
+            self.emitted_reward
+                .to_little_endian(&mut emitted_reward_bytes[..32]);
+
+            for i in 265..297 {
+                res.push(emitted_reward_bytes[i - 265])
+            }
+
+            let mut reward_per_second_bytes = [0_u8; 64];
+            self.reward_per_second
+                .to_little_endian(&mut reward_per_second_bytes[..32]);
+
+            for i in 297..329 {
+                res.push(reward_per_second_bytes[i - 297])
+            }
+
+            for i in self.cooldown.to_le_bytes() {
+                res.push(i)
+            }
+
+            res.push(self.emergency as u8);
             res
         }
 
@@ -3065,6 +4875,12 @@ mod tests {
             let total_reward = U256::from_little_endian(&src[136..168]);
             let acc_token_per_share = U256::from_little_endian(&src[168..200]);
             let total_staked = U256::from_little_endian(&src[200..232]);
+            let native_reward = src[232] != 0;
+            let unallocated_reward = U256::from_little_endian(&src[233..265]);
+            let emitted_reward = U256::from_little_endian(&src[265..297]);
+            let reward_per_second = U256::from_little_endian(&src[297..329]);
+            let cooldown = u64::from_le_bytes(src[329..337].try_into().unwrap());
+            let emergency = src[337] != 0;
 
             Self {
                 id,
@@ -3078,6 +4894,12 @@ mod tests {
                 total_reward,
                 acc_token_per_share,
                 total_staked,
+                native_reward,
+                unallocated_reward,
+                emitted_reward,
+                reward_per_second,
+                cooldown,
+                emergency,
             }
         }
 
@@ -0,0 +1,145 @@
+//! Stake history, kept as checkpoint lists for voting power lookups.
+extern crate alloc;
+
+use core::convert::TryInto;
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use casper_contract::{
+    contract_api::{runtime, runtime::revert, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+
+use casper_types::{ApiError, URef, U256};
+
+// bytes per packed checkpoint
+pub const CHECKPOINT_SIZE: usize = 40;
+
+pub struct Checkpoint {
+    // 8
+    pub timestamp: u64,
+    // 32 : 40
+    pub amount: U256,
+}
+
+impl Checkpoint {
+    pub fn pack(&self) -> [u8; CHECKPOINT_SIZE] {
+        let mut res = [0u8; CHECKPOINT_SIZE];
+
+        res[..8].copy_from_slice(&self.timestamp.to_le_bytes());
+
+        self.amount.to_little_endian(&mut res[8..40]);
+
+        res
+    }
+
+    pub fn unpack(src: &[u8]) -> Self {
+        if src.len() != CHECKPOINT_SIZE {
+            revert(ApiError::Formatting)
+        }
+
+        Checkpoint {
+            timestamp: u64::from_le_bytes(src[0..8].try_into().unwrap()),
+            amount: U256::from_little_endian(&src[8..40]),
+        }
+    }
+}
+
+// the checkpoints of one owner in a dictionary. Checkpoint n is stored
+// under "<owner>_<n>" and their count under "<owner>_count", so writes
+// and lookups only touch a few entries however long the history gets
+pub struct Checkpoints {
+    seed_uref: URef,
+    owner: String,
+}
+
+impl Checkpoints {
+    pub fn new(dictionary: &str, owner: &str) -> Self {
+        let seed_uref = *runtime::get_key(dictionary)
+            .unwrap_or_revert()
+            .as_uref()
+            .unwrap_or_revert();
+
+        Checkpoints {
+            seed_uref,
+            owner: owner.into(),
+        }
+    }
+
+    fn item_key(&self, index: u64) -> String {
+        self.owner.clone() + "_" + &index.to_string()
+    }
+
+    fn count_key(&self) -> String {
+        self.owner.clone() + "_count"
+    }
+
+    pub fn count(&self) -> u64 {
+        storage::dictionary_get::<u64>(self.seed_uref, &self.count_key())
+            .unwrap_or_revert()
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, index: u64) -> Checkpoint {
+        let bytes = storage::dictionary_get::<Vec<u8>>(self.seed_uref, &self.item_key(index))
+            .unwrap_or_revert()
+            .unwrap_or_revert_with(ApiError::MissingKey);
+
+        Checkpoint::unpack(&bytes)
+    }
+
+    // records amount as of timestamp after the last checkpoint,
+    // a checkpoint written at the same timestamp is replaced
+    pub fn push(&self, timestamp: u64, amount: U256) {
+        let count = self.count();
+
+        let mut index = count;
+
+        if count > 0 {
+            let last = self.get(count - 1);
+
+            if last.timestamp > timestamp {
+                revert(ApiError::InvalidArgument)
+            }
+            if last.timestamp == timestamp {
+                index = count - 1;
+            }
+        }
+
+        storage::dictionary_put::<Vec<u8>>(
+            self.seed_uref,
+            &self.item_key(index),
+            Checkpoint { timestamp, amount }.pack().to_vec(),
+        );
+
+        if index == count {
+            storage::dictionary_put::<u64>(self.seed_uref, &self.count_key(), count + 1);
+        }
+    }
+
+    // amount recorded by the latest checkpoint at or before
+    // timestamp, zero before the first checkpoint
+    pub fn amount_at(&self, timestamp: u64) -> U256 {
+        // binary search over the ascending timestamps
+        let mut low = 0;
+        let mut high = self.count();
+
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.get(mid).timestamp <= timestamp {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        if low == 0 {
+            U256::zero()
+        } else {
+            self.get(low - 1).amount
+        }
+    }
+}
@@ -1,48 +1,105 @@
-//! Constants used by the Staking contract.
-pub const CONTRACT_NAME: &str = "stake_contract";
-pub const CONTRACT_VERSION: &str = "CONT_VERSION_KEY";
-pub const CONTRACT_HASH: &str = "stake_conthash";
-
-pub const KEY_NAME_ADMIN: &str = "admin-account";
-pub const KEY_NAME_INDEX: &str = "index";
-pub const KEY_NAME_SELF_CONTRACT_HASH: &str = "self_contract_hash";
-pub const KEY_NAME_DIC_STAKE_INFOS: &str = "dic_stake_infos";
-pub const KEY_NAME_USER_INFOS: &str = "user_infos";
-pub const KEY_NAME_INITIALIZED: &str = "initialized";
-
-pub const VESTOR_PACKAGE_NAME: &str = "vestor_pack";
-pub const VESTOR_UREF_NAME: &str = "vestor_access_uref";
-
-pub const ARG_NAME_ERC20_TOKEN_HASH: &str = "token-hash";
-pub const ARG_NAME_ERC20_SELFCONTRACT_HASH: &str = "package-hash";
-pub const ARG_NAME_NEW_ADMIN: &str = "new_admin";
-pub const ARG_NAME_TOKEN_HASH: &str = "token_hash";
-pub const ARG_NAME_AMOUNT: &str = "amount";
-pub const ARG_NAME_POOL_ID: &str = "pool_id";
-pub const ARG_NAME_STAKING_TOKEN: &str = "staking_token";
-pub const ARG_NAME_REWARD_TOKEN: &str = "reward_token";
-pub const ARG_NAME_START_TIME: &str = "start_time";
-pub const ARG_NAME_END_TIME: &str = "end_time";
-pub const ARG_NAME_PRECISION: &str = "precision";
-pub const ARG_NAME_TOTAL_REWARD: &str = "total_reward";
-
-
-pub const ENTRYPOINT_NAME_INIT: &str = "init";
-pub const ENTRYPOINT_NAME_ADD_POOL: &str = "add_pool";
-pub const ENTRYPOINT_NAME_DEPOSIT: &str = "deposit";
-pub const ENTRYPOINT_NAME_WITHDRAW: &str = "withdraw";
-pub const ENTRYPOINT_NAME_EMERGENCY_WITHDRAW: &str = "emergency_withdraw";
-pub const ENTRYPOINT_NAME_STOP_REWARD: &str = "stop_reward";
-pub const ENTRYPOINT_NAME_SAVE_ME: &str = "save_me";
-pub const ENTRYPOINT_NAME_SET_ADMIN: &str = "set_admin";
-
-pub const KEY_NAME_TOKEN_HASH: &str = "token_hash";
-
-pub const ENTRY_POINT_NAME_BALANCE_OF: &str = "balance_of";
-pub const ENTRY_POINT_NAME_TRANSFER_FROM: &str = "transfer_from";
-pub const ENTRY_POINT_NAME_TRANSFER: &str = "transfer";
-pub const ARG_NAME_OWNER: &str = "owner";
-pub const ARG_NAME_SPENDER: &str = "spender";
-pub const ARG_NAME_RECIPIENT: &str = "recipient";
-
-
+//! Constants used by the Staking contract.
+pub const CONTRACT_NAME: &str = "stake_contract";
+pub const CONTRACT_VERSION: &str = "CONT_VERSION_KEY";
+pub const CONTRACT_HASH: &str = "stake_conthash";
+
+pub const KEY_NAME_ADMIN: &str = "admin-account";
+pub const KEY_NAME_INDEX: &str = "index";
+pub const KEY_NAME_SELF_CONTRACT_HASH: &str = "self_contract_hash";
+pub const KEY_NAME_DIC_STAKE_INFOS: &str = "dic_stake_infos";
+pub const KEY_NAME_USER_INFOS: &str = "user_infos";
+pub const KEY_NAME_INITIALIZED: &str = "initialized";
+pub const KEY_NAME_REWARD_PURSE: &str = "reward_purse_";
+pub const KEY_NAME_USER_CHECKPOINTS: &str = "user_checkpoints";
+pub const KEY_NAME_POOL_CHECKPOINTS: &str = "pool_checkpoints";
+pub const KEY_NAME_POOL_METADATA: &str = "pool_metadata";
+pub const KEY_NAME_UNBONDS: &str = "unbonds";
+pub const KEY_NAME_REENTRANCY_LOCK: &str = "reentrancy_lock";
+pub const KEY_NAME_SINGLE_POOL: &str = "single_pool";
+pub const KEY_NAME_POOL_CONTRACTS: &str = "pool_contracts";
+pub const KEY_NAME_POOL_CONTRACT_COUNT: &str = "pool_contract_count";
+
+pub const VESTOR_PACKAGE_NAME: &str = "vestor_pack";
+pub const VESTOR_UREF_NAME: &str = "vestor_access_uref";
+
+pub const ARG_NAME_ERC20_TOKEN_HASH: &str = "token-hash";
+pub const ARG_NAME_ERC20_SELFCONTRACT_HASH: &str = "package-hash";
+pub const ARG_NAME_NEW_ADMIN: &str = "new_admin";
+pub const ARG_NAME_TOKEN_HASH: &str = "token_hash";
+pub const ARG_NAME_AMOUNT: &str = "amount";
+pub const ARG_NAME_POOL_ID: &str = "pool_id";
+pub const ARG_NAME_INDEX: &str = "index";
+pub const ARG_NAME_STAKING_TOKEN: &str = "staking_token";
+pub const ARG_NAME_REWARD_TOKEN: &str = "reward_token";
+pub const ARG_NAME_START_TIME: &str = "start_time";
+pub const ARG_NAME_END_TIME: &str = "end_time";
+pub const ARG_NAME_PRECISION: &str = "precision";
+pub const ARG_NAME_TOTAL_REWARD: &str = "total_reward";
+pub const ARG_NAME_PURSE: &str = "purse";
+pub const ARG_NAME_EMISSION_CURVE: &str = "emission_curve";
+pub const ARG_NAME_EPOCHS: &str = "epochs";
+pub const ARG_NAME_HALVING_PERIOD: &str = "halving_period";
+pub const ARG_NAME_POOL_IDS: &str = "pool_ids";
+pub const ARG_NAME_AMOUNTS: &str = "amounts";
+pub const ARG_NAME_ACCOUNT: &str = "account";
+pub const ARG_NAME_TIMESTAMP: &str = "timestamp";
+pub const ARG_NAME_NAME: &str = "name";
+pub const ARG_NAME_DESCRIPTION: &str = "description";
+pub const ARG_NAME_URL: &str = "url";
+pub const ARG_NAME_REWARD_PER_SECOND: &str = "reward_per_second";
+pub const ARG_NAME_COOLDOWN: &str = "cooldown";
+pub const ARG_NAME_EMERGENCY: &str = "emergency";
+
+// longest name, description or url a pool can store
+pub const MAX_POOL_METADATA_LENGTH: usize = 1024;
+
+// longest unbonding cooldown a pool can require, 30 days
+pub const MAX_COOLDOWN: u64 = 2_592_000;
+
+
+pub const ENTRYPOINT_NAME_INIT: &str = "init";
+pub const ENTRYPOINT_NAME_ADD_POOL: &str = "add_pool";
+pub const ENTRYPOINT_NAME_ADD_NATIVE_POOL: &str = "add_native_pool";
+pub const ENTRYPOINT_NAME_DEPOSIT: &str = "deposit";
+pub const ENTRYPOINT_NAME_WITHDRAW: &str = "withdraw";
+pub const ENTRYPOINT_NAME_EMERGENCY_WITHDRAW: &str = "emergency_withdraw";
+pub const ENTRYPOINT_NAME_STOP_REWARD: &str = "stop_reward";
+pub const ENTRYPOINT_NAME_SAVE_ME: &str = "save_me";
+pub const ENTRYPOINT_NAME_SET_ADMIN: &str = "set_admin";
+pub const ENTRYPOINT_NAME_SET_EMISSION_CURVE: &str = "set_emission_curve";
+pub const ENTRYPOINT_NAME_RECLAIM_UNALLOCATED: &str = "reclaim_unallocated";
+pub const ENTRYPOINT_NAME_HARVEST_MANY: &str = "harvest_many";
+pub const ENTRYPOINT_NAME_WITHDRAW_MANY: &str = "withdraw_many";
+pub const ENTRYPOINT_NAME_MASS_UPDATE_POOLS: &str = "mass_update_pools";
+pub const ENTRYPOINT_NAME_STAKE_AT: &str = "stake_at";
+pub const ENTRYPOINT_NAME_TOTAL_STAKED_AT: &str = "total_staked_at";
+pub const ENTRYPOINT_NAME_GET_POOL: &str = "get_pool";
+pub const ENTRYPOINT_NAME_GET_USER_INFO: &str = "get_user_info";
+pub const ENTRYPOINT_NAME_SET_POOL_METADATA: &str = "set_pool_metadata";
+pub const ENTRYPOINT_NAME_GET_POOL_METADATA: &str = "get_pool_metadata";
+pub const ENTRYPOINT_NAME_ADD_RATE_POOL: &str = "add_rate_pool";
+pub const ENTRYPOINT_NAME_TOP_UP_POOL: &str = "top_up_pool";
+pub const ENTRYPOINT_NAME_SET_COOLDOWN: &str = "set_cooldown";
+pub const ENTRYPOINT_NAME_SET_EMERGENCY: &str = "set_emergency";
+pub const ENTRYPOINT_NAME_REQUEST_WITHDRAW: &str = "request_withdraw";
+pub const ENTRYPOINT_NAME_COMPLETE_WITHDRAW: &str = "complete_withdraw";
+pub const ENTRYPOINT_NAME_CREATE_POOL_CONTRACT: &str = "create_pool_contract";
+pub const ENTRYPOINT_NAME_GET_POOL_CONTRACT: &str = "get_pool_contract";
+pub const ENTRYPOINT_NAME_GET_POOL_CONTRACT_COUNT: &str = "get_pool_contract_count";
+
+pub const KEY_NAME_TOKEN_HASH: &str = "token_hash";
+
+pub const ENTRY_POINT_NAME_BALANCE_OF: &str = "balance_of";
+pub const ENTRY_POINT_NAME_TRANSFER_FROM: &str = "transfer_from";
+pub const ENTRY_POINT_NAME_TRANSFER: &str = "transfer";
+pub const ENTRY_POINT_NAME_DECIMALS: &str = "decimals";
+pub const ENTRY_POINT_NAME_APPROVE: &str = "approve";
+pub const ARG_NAME_OWNER: &str = "owner";
+pub const ARG_NAME_SPENDER: &str = "spender";
+pub const ARG_NAME_RECIPIENT: &str = "recipient";
+pub const ARG_NAME_ADDRESS: &str = "address";
+
+// motes per CSPR
+pub const CSPR_DECIMALS: u8 = 9;
+
+
@@ -0,0 +1,297 @@
+//! Reward emission schedules for stake pools.
+extern crate alloc;
+
+use core::convert::TryInto;
+
+use alloc::vec::Vec;
+
+use casper_contract::{contract_api::runtime::revert, unwrap_or_revert::UnwrapOrRevert};
+
+use casper_types::{ApiError, U256};
+
+use crate::{
+    math::{self, mul_div},
+    Error,
+};
+
+pub const EMISSION_LINEAR: u8 = 0;
+pub const EMISSION_EPOCHS: u8 = 1;
+pub const EMISSION_LINEAR_DECAY: u8 = 2;
+pub const EMISSION_HALVING: u8 = 3;
+
+// a halving pool can't span more periods than this
+pub const MAX_HALVINGS: u64 = 64;
+
+#[derive(Clone, Copy)]
+pub struct Epoch {
+    // 8
+    pub end: u64,
+    // 32 : 40
+    pub amount: U256,
+}
+
+pub enum EmissionCurve {
+    // total_reward is spread evenly over start_time..end_time
+    Linear,
+    // each epoch emits its amount evenly until the epoch ends
+    Epochs(Vec<Epoch>),
+    // the rate falls linearly and reaches zero at end_time
+    LinearDecay,
+    // the rate halves every period (seconds)
+    Halving(u64),
+}
+
+impl EmissionCurve {
+    // builds a curve from entry point args
+    // @kind - one of the EMISSION_* ids
+    // @epochs - (end, amount) pairs, only used by EMISSION_EPOCHS
+    // @halving_period - only used by EMISSION_HALVING
+    pub fn new(kind: u8, epochs: Vec<(u64, U256)>, halving_period: u64) -> Self {
+        match kind {
+            EMISSION_LINEAR => Self::Linear,
+            EMISSION_EPOCHS => Self::Epochs(
+                epochs
+                    .iter()
+                    .map(|(end, amount)| Epoch {
+                        end: *end,
+                        amount: *amount,
+                    })
+                    .collect(),
+            ),
+            EMISSION_LINEAR_DECAY => Self::LinearDecay,
+            EMISSION_HALVING => Self::Halving(halving_period),
+            _ => revert(ApiError::InvalidArgument),
+        }
+    }
+
+    // the EMISSION_* id of the curve
+    pub fn kind(&self) -> u8 {
+        match self {
+            Self::Linear => EMISSION_LINEAR,
+            Self::Epochs(_) => EMISSION_EPOCHS,
+            Self::LinearDecay => EMISSION_LINEAR_DECAY,
+            Self::Halving(_) => EMISSION_HALVING,
+        }
+    }
+
+    // returns the curve as a byte array: kind followed by its params
+    pub fn pack(&self) -> Vec<u8> {
+        let mut res: Vec<u8> = Vec::new();
+
+        match self {
+            Self::Linear => res.push(EMISSION_LINEAR),
+            Self::Epochs(epochs) => {
+                res.push(EMISSION_EPOCHS);
+                for epoch in epochs.iter() {
+                    for i in Self::pack_epoch(epoch) {
+                        res.push(i)
+                    }
+                }
+            }
+            Self::LinearDecay => res.push(EMISSION_LINEAR_DECAY),
+            Self::Halving(period) => {
+                res.push(EMISSION_HALVING);
+                for i in period.to_le_bytes() {
+                    res.push(i)
+                }
+            }
+        }
+
+        res
+    }
+
+    fn pack_epoch(src: &Epoch) -> [u8; 40] {
+        let mut res = [0u8; 40];
+
+        let mut little_endian_bytes = [0_u8; 64];
+
+        src.amount.to_little_endian(&mut little_endian_bytes[..32]);
+
+        res[..8].copy_from_slice(&src.end.to_le_bytes());
+
+        res[8..40].copy_from_slice(&little_endian_bytes[..32]);
+
+        res
+    }
+
+    pub fn unpack(src: &[u8]) -> Self {
+        if src.is_empty() {
+            revert(ApiError::Formatting)
+        }
+
+        let (kind, params) = src.split_at(1);
+
+        match kind[0] {
+            EMISSION_LINEAR => Self::Linear,
+            EMISSION_EPOCHS => Self::Epochs(Self::unpack_epochs(params)),
+            EMISSION_LINEAR_DECAY => Self::LinearDecay,
+            EMISSION_HALVING => Self::Halving(u64::from_le_bytes(params[0..8].try_into().unwrap())),
+            _ => revert(ApiError::Formatting),
+        }
+    }
+
+    fn unpack_epochs(src: &[u8]) -> Vec<Epoch> {
+        if src.len() % 40 != 0 {
+            revert(ApiError::Formatting)
+        }
+
+        src.chunks(40)
+            .map(|epoch| Epoch {
+                end: u64::from_le_bytes(epoch[0..8].try_into().unwrap()),
+                amount: U256::from_little_endian(&epoch[8..40]),
+            })
+            .collect()
+    }
+
+    // reverts unless the curve emits exactly total_reward
+    // between start and end
+    pub fn validate(&self, start: u64, end: u64, total_reward: U256) {
+        match self {
+            Self::Linear | Self::LinearDecay => {}
+            Self::Epochs(epochs) => {
+                let mut epoch_start = start;
+                let mut sum = U256::zero();
+
+                for epoch in epochs.iter() {
+                    if epoch.end <= epoch_start {
+                        revert(ApiError::InvalidArgument)
+                    }
+                    sum = sum
+                        .checked_add(epoch.amount)
+                        .unwrap_or_revert_with(ApiError::InvalidArgument);
+                    epoch_start = epoch.end;
+                }
+
+                // epochs must cover the whole pool
+                if epoch_start != end || sum != total_reward {
+                    revert(ApiError::InvalidArgument)
+                }
+            }
+            Self::Halving(period) => {
+                let duration = end
+                    .checked_sub(start)
+                    .unwrap_or_revert_with(Error::Underflow);
+
+                if *period == 0 || halving_periods(*period, duration) > MAX_HALVINGS {
+                    revert(ApiError::InvalidArgument)
+                }
+            }
+        }
+    }
+
+    // rewards emitted between from and to, both within start..=end
+    pub fn rewards_between(
+        &self,
+        total_reward: U256,
+        start: u64,
+        end: u64,
+        from: u64,
+        to: u64,
+    ) -> Result<U256, Error> {
+        match self {
+            Self::Linear => mul_div(
+                math::sub(U256::from(to), U256::from(from))?,
+                total_reward,
+                math::sub(U256::from(end), U256::from(start))?,
+            ),
+            _ => math::sub(
+                self.emitted(total_reward, start, end, to)?,
+                self.emitted(total_reward, start, end, from)?,
+            ),
+        }
+    }
+
+    // total rewards emitted from start until at
+    fn emitted(&self, total_reward: U256, start: u64, end: u64, at: u64) -> Result<U256, Error> {
+        let elapsed = at.checked_sub(start).ok_or(Error::Underflow)?;
+        let duration = end.checked_sub(start).ok_or(Error::Underflow)?;
+
+        match self {
+            Self::Linear => mul_div(total_reward, U256::from(elapsed), U256::from(duration)),
+            Self::Epochs(epochs) => {
+                let mut emitted = U256::zero();
+                let mut epoch_start = start;
+
+                for epoch in epochs.iter() {
+                    if at < epoch.end {
+                        if at > epoch_start {
+                            emitted = math::add(
+                                emitted,
+                                mul_div(
+                                    epoch.amount,
+                                    math::sub(U256::from(at), U256::from(epoch_start))?,
+                                    math::sub(U256::from(epoch.end), U256::from(epoch_start))?,
+                                )?,
+                            )?;
+                        }
+                        break;
+                    }
+                    emitted = math::add(emitted, epoch.amount)?;
+                    epoch_start = epoch.end;
+                }
+
+                Ok(emitted)
+            }
+            Self::LinearDecay => {
+                // integral of the falling rate is x(2T - x) / T^2
+                let x = U256::from(elapsed);
+                let t = U256::from(duration);
+                mul_div(
+                    total_reward,
+                    math::mul(x, math::sub(math::mul(t, U256::from(2))?, x)?)?,
+                    math::mul(t, t)?,
+                )
+            }
+            Self::Halving(period) => mul_div(
+                total_reward,
+                halving_weight(elapsed, *period, duration)?,
+                halving_weight(duration, *period, duration)?,
+            ),
+        }
+    }
+}
+
+// number of (possibly partial) halving periods in duration
+fn halving_periods(period: u64, duration: u64) -> u64 {
+    duration / period + u64::from(duration % period != 0)
+}
+
+// cumulative weight of a rate halving every period, scaled
+// so the rate in the last period of duration is 1
+fn halving_weight(elapsed: u64, period: u64, duration: u64) -> Result<U256, Error> {
+    let periods = halving_periods(period, duration);
+    let full_periods = elapsed / period;
+
+    let mut weight = U256::zero();
+
+    for k in 0..full_periods {
+        weight = math::add(
+            weight,
+            math::mul(U256::from(period), halving_rate(periods, k)?)?,
+        )?;
+    }
+
+    if full_periods < periods {
+        weight = math::add(
+            weight,
+            math::mul(
+                U256::from(elapsed % period),
+                halving_rate(periods, full_periods)?,
+            )?,
+        )?;
+    }
+
+    Ok(weight)
+}
+
+// rate of period k out of periods, 2^(periods - 1 - k)
+fn halving_rate(periods: u64, k: u64) -> Result<U256, Error> {
+    let halvings_left = periods
+        .checked_sub(1)
+        .and_then(|last| last.checked_sub(k))
+        .ok_or(Error::Underflow)?;
+
+    U256::from(2)
+        .checked_pow(U256::from(halvings_left))
+        .ok_or(Error::Overflow)
+}
@@ -0,0 +1,133 @@
+extern crate alloc;
+
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+};
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+
+use casper_types::{
+    account::AccountHash, contracts::EntryPoints, runtime_args, ApiError, ContractHash,
+    ContractPackageHash, Key, RuntimeArgs, URef, U256,
+};
+
+use crate::{interact_token::interact_erc20, utils, Address, Error};
+
+use crate::constants::{
+    ARG_NAME_ERC20_SELFCONTRACT_HASH, ARG_NAME_TOTAL_REWARD, CONTRACT_NAME, ENTRYPOINT_NAME_INIT,
+    KEY_NAME_ADMIN, KEY_NAME_INITIALIZED, KEY_NAME_POOL_CONTRACTS, KEY_NAME_POOL_CONTRACT_COUNT,
+    KEY_NAME_REENTRANCY_LOCK, KEY_NAME_SELF_CONTRACT_HASH, KEY_NAME_SINGLE_POOL,
+};
+
+#[derive(Default)]
+pub struct PoolFactory;
+
+impl PoolFactory {
+    // installs a fresh staking contract with entry_points, running
+    // this contract's code, so a pool keeps its tokens apart from
+    // every other pool. The factory's admin becomes its admin, so
+    // the caller can't take the stakes out with save_me. The caller
+    // owns its pool, which is funded with total_reward from the
+    // caller. The caller approves this contract for total_reward first.
+    // @add_pool_entry_point - add_pool or add_rate_pool
+    // @pool_args - args of add_pool_entry_point but total_reward
+    // returns the contract and package hash of the new contract
+    pub fn create_pool_contract(
+        &self,
+        entry_points: EntryPoints,
+        add_pool_entry_point: &str,
+        reward_token: ContractHash,
+        total_reward: U256,
+        mut pool_args: RuntimeArgs,
+    ) -> (ContractHash, ContractPackageHash) {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        let mut named_keys: BTreeMap<String, Key> = BTreeMap::new();
+        named_keys.insert(String::from(CONTRACT_NAME), storage::new_uref(0_i32).into());
+        named_keys.insert(
+            String::from(KEY_NAME_ADMIN),
+            storage::new_uref(utils::get_key::<AccountHash>(KEY_NAME_ADMIN)).into(),
+        );
+        named_keys.insert(
+            String::from(KEY_NAME_INITIALIZED),
+            storage::new_uref(false).into(),
+        );
+        named_keys.insert(
+            String::from(KEY_NAME_REENTRANCY_LOCK),
+            storage::new_uref(false).into(),
+        );
+        named_keys.insert(
+            String::from(KEY_NAME_SINGLE_POOL),
+            storage::new_uref(true).into(),
+        );
+
+        // the access uref isn't kept, so nobody can upgrade the new contract
+        let (package_hash, _) = storage::create_contract_package_at_hash();
+        let (contract_hash, _) =
+            storage::add_contract_version(package_hash, entry_points, named_keys);
+
+        runtime::call_contract::<()>(
+            contract_hash,
+            ENTRYPOINT_NAME_INIT,
+            runtime_args! {
+                ARG_NAME_ERC20_SELFCONTRACT_HASH => Key::from(package_hash)
+            },
+        );
+
+        // the reward passes through this contract, so a contract only
+        // gets registered once its pool is funded
+        let received = interact_erc20::default().transfer_from(
+            reward_token,
+            Address::from(runtime::get_caller()),
+            utils::get_key(KEY_NAME_SELF_CONTRACT_HASH),
+            total_reward,
+        );
+
+        interact_erc20::default().approve(reward_token, package_hash, received);
+
+        pool_args
+            .insert(ARG_NAME_TOTAL_REWARD, received)
+            .unwrap_or_revert();
+
+        runtime::call_contract::<u64>(contract_hash, add_pool_entry_point, pool_args);
+
+        let count = self.get_pool_contract_count();
+
+        storage::dictionary_put(
+            registry_uref(),
+            &count.to_string(),
+            (contract_hash, package_hash),
+        );
+
+        utils::set_key(
+            KEY_NAME_POOL_CONTRACT_COUNT,
+            count.checked_add(1).unwrap_or_revert_with(Error::Overflow),
+        );
+
+        (contract_hash, package_hash)
+    }
+
+    // number of contracts in the registry
+    pub fn get_pool_contract_count(&self) -> u64 {
+        utils::get_key(KEY_NAME_POOL_CONTRACT_COUNT)
+    }
+
+    // contract and package hash of the pool contract at index,
+    // indexes run from 0 to pool_contract_count - 1
+    pub fn get_pool_contract(&self, index: u64) -> (ContractHash, ContractPackageHash) {
+        storage::dictionary_get(registry_uref(), &index.to_string())
+            .unwrap_or_revert()
+            .unwrap_or_revert_with(ApiError::InvalidArgument)
+    }
+}
+
+fn registry_uref() -> URef {
+    *runtime::get_key(KEY_NAME_POOL_CONTRACTS)
+        .unwrap_or_revert()
+        .as_uref()
+        .unwrap_or_revert()
+}
@@ -1,86 +1,140 @@
-//#![no_std]
-#![no_main]
-#![allow(non_snake_case)]
-#![allow(unused_imports)]
-#![allow(non_camel_case_types)]
-#![allow(unused_attributes)]
-
-// #[cfg(not(target_arch = "wasm32"))]
-// compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
-
-extern crate alloc;
-use core::str::FromStr;
-
-use casper_contract::{contract_api::{runtime, storage}, unwrap_or_revert::UnwrapOrRevert};
-use casper_types::{runtime_args, ContractHash, Key, RuntimeArgs, account::AccountHash, U256, ContractPackageHash, bytesrepr::ToBytes};
-
-use alloc::{
-    collections::{BTreeMap}, //BTreeSet},
-    string::String, borrow::ToOwned
-};
-
-use crate::utils;
-use crate::{Error, Address};
-
-use crate::{
-    constants::{
-        ENTRY_POINT_NAME_BALANCE_OF,
-        ENTRY_POINT_NAME_TRANSFER_FROM,
-        ENTRY_POINT_NAME_TRANSFER,
-        ARG_NAME_OWNER,
-        ARG_NAME_AMOUNT,
-        KEY_NAME_TOKEN_HASH,
-        ARG_NAME_SPENDER,
-        ARG_NAME_RECIPIENT,
-    },
-};
-
-
-/*
-implement ERC20 functionality for vesting
-*/
-#[derive(Default)]
-pub struct interact_erc20;
-
-impl interact_erc20
-{
-
-    pub fn get_token_hash(&self) -> ContractHash
-    {
-        utils::get_key(KEY_NAME_TOKEN_HASH)
-    }
-
-    pub fn transfer_from(&mut self
-        , hash_token: ContractHash
-        , owner: AccountHash
-        , spender: ContractPackageHash
-        , amount: U256
-    ) 
-    {
-        runtime::call_contract(
-            hash_token,                            //contracthash
-            ENTRY_POINT_NAME_TRANSFER_FROM,
-            runtime_args! {
-                ARG_NAME_OWNER => Address::from(owner),         //owner : AccountHash
-                ARG_NAME_RECIPIENT => Address::from(spender),   //spender: AccountHash
-                ARG_NAME_AMOUNT => amount
-            },
-        )
-    }
-
-    pub fn transfer(&mut self
-        , hash_token: ContractHash
-        , recipient: AccountHash
-        , amount: U256
-    ) 
-    {        
-        runtime::call_contract(
-            hash_token, //self.get_token_hash(), 
-            ENTRY_POINT_NAME_TRANSFER,
-            runtime_args! {
-                ARG_NAME_RECIPIENT => Address::from(recipient),
-                ARG_NAME_AMOUNT => amount
-            },
-        )
-    }
-}
+//#![no_std]
+#![no_main]
+#![allow(non_snake_case)]
+#![allow(unused_imports)]
+#![allow(non_camel_case_types)]
+#![allow(unused_attributes)]
+
+// #[cfg(not(target_arch = "wasm32"))]
+// compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+extern crate alloc;
+use core::str::FromStr;
+
+use casper_contract::{contract_api::{runtime, storage}, unwrap_or_revert::UnwrapOrRevert};
+use casper_types::{runtime_args, ContractHash, Key, RuntimeArgs, account::AccountHash, U256, ContractPackageHash, bytesrepr::ToBytes, ApiError};
+
+use alloc::{
+    collections::{BTreeMap}, //BTreeSet},
+    string::String, borrow::ToOwned
+};
+
+use crate::utils;
+use crate::{Error, Address};
+
+use crate::{
+    constants::{
+        ENTRY_POINT_NAME_BALANCE_OF,
+        ENTRY_POINT_NAME_TRANSFER_FROM,
+        ENTRY_POINT_NAME_TRANSFER,
+        ENTRY_POINT_NAME_DECIMALS,
+        ENTRY_POINT_NAME_APPROVE,
+        ARG_NAME_OWNER,
+        ARG_NAME_AMOUNT,
+        KEY_NAME_TOKEN_HASH,
+        ARG_NAME_SPENDER,
+        ARG_NAME_RECIPIENT,
+        ARG_NAME_ADDRESS,
+    },
+};
+
+
+/*
+implement ERC20 functionality for vesting
+*/
+#[derive(Default)]
+pub struct interact_erc20;
+
+impl interact_erc20
+{
+
+    pub fn get_token_hash(&self) -> ContractHash
+    {
+        utils::get_key(KEY_NAME_TOKEN_HASH)
+    }
+
+    pub fn balance_of(&self
+        , hash_token: ContractHash
+        , address: Address
+    ) -> U256
+    {
+        runtime::call_contract(
+            hash_token,
+            ENTRY_POINT_NAME_BALANCE_OF,
+            runtime_args! {
+                ARG_NAME_ADDRESS => address
+            },
+        )
+    }
+
+    pub fn decimals(&self
+        , hash_token: ContractHash
+    ) -> u8
+    {
+        runtime::call_contract(
+            hash_token,
+            ENTRY_POINT_NAME_DECIMALS,
+            runtime_args! {},
+        )
+    }
+
+    // returns the amount that actually arrived at spender,
+    // which is less than amount for fee-on-transfer tokens
+    pub fn transfer_from(&mut self
+        , hash_token: ContractHash
+        , owner: Address
+        , spender: ContractPackageHash
+        , amount: U256
+    ) -> U256
+    {
+        let balance_before = self.balance_of(hash_token, Address::from(spender));
+
+        runtime::call_contract::<()>(
+            hash_token,                            //contracthash
+            ENTRY_POINT_NAME_TRANSFER_FROM,
+            runtime_args! {
+                ARG_NAME_OWNER => owner,                        //owner : Address
+                ARG_NAME_RECIPIENT => Address::from(spender),   //spender: AccountHash
+                ARG_NAME_AMOUNT => amount
+            },
+        );
+
+        let balance_after = self.balance_of(hash_token, Address::from(spender));
+
+        balance_after
+            .checked_sub(balance_before)
+            .unwrap_or_revert_with(ApiError::InvalidArgument)
+    }
+
+    pub fn approve(&mut self
+        , hash_token: ContractHash
+        , spender: ContractPackageHash
+        , amount: U256
+    )
+    {
+        runtime::call_contract::<()>(
+            hash_token,
+            ENTRY_POINT_NAME_APPROVE,
+            runtime_args! {
+                ARG_NAME_SPENDER => Address::from(spender),
+                ARG_NAME_AMOUNT => amount
+            },
+        )
+    }
+
+    pub fn transfer(&mut self
+        , hash_token: ContractHash
+        , recipient: Address
+        , amount: U256
+    ) 
+    {        
+        runtime::call_contract(
+            hash_token, //self.get_token_hash(), 
+            ENTRY_POINT_NAME_TRANSFER,
+            runtime_args! {
+                ARG_NAME_RECIPIENT => recipient,
+                ARG_NAME_AMOUNT => amount
+            },
+        )
+    }
+}
@@ -1,53 +1,99 @@
-#![cfg_attr(not(feature = "std"), no_std)]
-
-pub mod constants;
-pub mod interact_token;
-pub mod pool;
-pub mod utils;
-
-mod stake;
-pub use stake::StakeContract;
-
-mod address;
-pub use address::Address;
-
-#[derive(PartialEq, Eq, Debug)]
-pub enum VestingError {
-    NotEnoughBalance,
-    AdminReleaseErrorNotPaused,
-    AdminReleaseErrorNothingToWithdraw,
-    AdminReleaseErrorNotEnoughTimeElapsed,
-    AlreadyPaused,
-    AlreadyUnpaused,
-}
-
-#[derive(PartialEq, Eq, Debug)]
-pub enum VestError {
-    AlreadyPaused = 1,
-    AlreadyUnpaused = 2,
-    NotTheAdminAccount = 3,
-    NotTheRecipientAccount = 4,
-    UnexpectedVestingError = 5,
-    NotEnoughBalance = 6,
-    PurseTransferError = 7,
-    NotPaused = 8,
-    NothingToWithdraw = 9,
-    NotEnoughTimeElapsed = 10,
-    LocalPurseKeyMissing = 11,
-    UnexpectedType = 12,
-    MissingKey = 13,
-}
-
-#[derive(PartialEq, Eq, Debug)]
-pub enum Error {
-    /// ERC20 contract called from within an invalid context.
-    InvalidContext,
-    /// Spender does not have enough balance.
-    InsufficientBalance,
-    /// Spender does not have enough allowance approved.
-    InsufficientAllowance,
-    /// Operation would cause an integer overflow.
-    Overflow,
-    /// User error.
-    User(u16),
-}
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod checkpoint;
+pub mod constants;
+pub mod emission;
+pub mod interact_token;
+pub mod math;
+pub mod pool;
+pub mod utils;
+
+mod stake;
+pub use stake::StakeContract;
+
+mod factory;
+pub use factory::PoolFactory;
+
+mod address;
+pub use address::Address;
+
+use casper_types::ApiError;
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum VestingError {
+    NotEnoughBalance,
+    AdminReleaseErrorNotPaused,
+    AdminReleaseErrorNothingToWithdraw,
+    AdminReleaseErrorNotEnoughTimeElapsed,
+    AlreadyPaused,
+    AlreadyUnpaused,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum VestError {
+    AlreadyPaused = 1,
+    AlreadyUnpaused = 2,
+    NotTheAdminAccount = 3,
+    NotTheRecipientAccount = 4,
+    UnexpectedVestingError = 5,
+    NotEnoughBalance = 6,
+    PurseTransferError = 7,
+    NotPaused = 8,
+    NothingToWithdraw = 9,
+    NotEnoughTimeElapsed = 10,
+    LocalPurseKeyMissing = 11,
+    UnexpectedType = 12,
+    MissingKey = 13,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum Error {
+    /// ERC20 contract called from within an invalid context.
+    InvalidContext,
+    /// Spender does not have enough balance.
+    InsufficientBalance,
+    /// Spender does not have enough allowance approved.
+    InsufficientAllowance,
+    /// Operation would cause an integer overflow.
+    Overflow,
+    /// Entry point called while another one is running.
+    Reentrant,
+    /// Operation would cause an integer underflow.
+    Underflow,
+    /// Amount is larger than the caller's stake.
+    InsufficientStake,
+    /// Pool is in emergency mode.
+    EmergencyMode,
+    /// Pool is not in emergency mode.
+    NotEmergencyMode,
+    /// User error.
+    User(u16),
+}
+
+const ERROR_INVALID_CONTEXT: u16 = u16::MAX;
+const ERROR_INSUFFICIENT_BALANCE: u16 = u16::MAX - 1;
+const ERROR_INSUFFICIENT_ALLOWANCE: u16 = u16::MAX - 2;
+const ERROR_OVERFLOW: u16 = u16::MAX - 3;
+const ERROR_REENTRANT: u16 = u16::MAX - 4;
+const ERROR_UNDERFLOW: u16 = u16::MAX - 5;
+const ERROR_INSUFFICIENT_STAKE: u16 = u16::MAX - 6;
+const ERROR_EMERGENCY_MODE: u16 = u16::MAX - 7;
+const ERROR_NOT_EMERGENCY_MODE: u16 = u16::MAX - 8;
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        let user_error = match error {
+            Error::InvalidContext => ERROR_INVALID_CONTEXT,
+            Error::InsufficientBalance => ERROR_INSUFFICIENT_BALANCE,
+            Error::InsufficientAllowance => ERROR_INSUFFICIENT_ALLOWANCE,
+            Error::Overflow => ERROR_OVERFLOW,
+            Error::Reentrant => ERROR_REENTRANT,
+            Error::Underflow => ERROR_UNDERFLOW,
+            Error::InsufficientStake => ERROR_INSUFFICIENT_STAKE,
+            Error::EmergencyMode => ERROR_EMERGENCY_MODE,
+            Error::NotEmergencyMode => ERROR_NOT_EMERGENCY_MODE,
+            Error::User(user_error) => user_error,
+        };
+        ApiError::User(user_error)
+    }
+}
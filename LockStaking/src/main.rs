@@ -1,230 +1,847 @@
-#![no_std]
-#![no_main]
-#[macro_use]
-// #[cfg(not(target_arch = "wasm32"))]
-// compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
-
-// We need to explicitly import the std alloc crate and `alloc::string::String` as we're in a
-// `no_std` environment.
-extern crate alloc;
-
-use alloc::{collections::BTreeMap, string::String};
-
-use casper_contract::contract_api::{runtime, storage};
-use casper_types::{
-    account::AccountHash,
-    contracts::{EntryPoint, EntryPointAccess, EntryPointType, EntryPoints},
-    CLType, CLTyped, ContractHash, ContractPackageHash, Key, Parameter, U256,
-};
-
-use lock_staking::constants::{
-    ARG_NAME_AMOUNT, ARG_NAME_END_TIME, ARG_NAME_ERC20_SELFCONTRACT_HASH, ARG_NAME_NEW_ADMIN,
-    ARG_NAME_POOL_ID, ARG_NAME_PRECISION, ARG_NAME_REWARD_TOKEN, ARG_NAME_STAKING_TOKEN,
-    ARG_NAME_START_TIME, ARG_NAME_TOKEN_HASH, ARG_NAME_TOTAL_REWARD, CONTRACT_HASH, CONTRACT_NAME,
-    CONTRACT_VERSION, ENTRYPOINT_NAME_ADD_POOL, ENTRYPOINT_NAME_DEPOSIT,
-    ENTRYPOINT_NAME_EMERGENCY_WITHDRAW, ENTRYPOINT_NAME_INIT, ENTRYPOINT_NAME_SAVE_ME,
-    ENTRYPOINT_NAME_SET_ADMIN, ENTRYPOINT_NAME_STOP_REWARD, ENTRYPOINT_NAME_WITHDRAW,
-    KEY_NAME_INITIALIZED, VESTOR_PACKAGE_NAME, VESTOR_UREF_NAME,
-};
-use lock_staking::{constants::KEY_NAME_ADMIN, StakeContract};
-
-// All the calls and their arg names are compiled into Entry Points for the runtime.
-// #[no_mangle] macro ensures that the function name will be the same string in WASM.
-#[no_mangle]
-pub extern "C" fn call() {
-    // The key shouldn't already exist in the named keys.
-    let counter_local_key = storage::new_uref(0_i32);
-
-    let mut vestor_named_keys: BTreeMap<String, Key> = BTreeMap::new();
-    let key_name = String::from(CONTRACT_NAME);
-    vestor_named_keys.insert(key_name, counter_local_key.into());
-
-    let admin_key = storage::new_uref(runtime::get_caller());
-    vestor_named_keys.insert(String::from(KEY_NAME_ADMIN), admin_key.into());
-
-    let initialized_key = storage::new_uref(false);
-    vestor_named_keys.insert(String::from(KEY_NAME_INITIALIZED), initialized_key.into());
-
-    let (stored_contract_hash, contract_version) = storage::new_locked_contract(
-        get_entry_points(),
-        Some(vestor_named_keys),
-        Some(String::from(VESTOR_PACKAGE_NAME)),
-        Some(String::from(VESTOR_UREF_NAME)),
-    );
-
-    let version_uref = storage::new_uref(contract_version);
-
-    runtime::put_key(CONTRACT_VERSION, version_uref.into());
-
-    runtime::put_key(CONTRACT_HASH, stored_contract_hash.into());
-}
-
-#[no_mangle]
-pub extern "C" fn init() {
-    let contract_package_hash: Key = runtime::get_named_arg(ARG_NAME_ERC20_SELFCONTRACT_HASH);
-    let hash = contract_package_hash.into_hash();
-    let p_hash = ContractPackageHash::new(hash.unwrap());
-    StakeContract::default().init(p_hash);
-}
-
-#[no_mangle]
-pub extern "C" fn add_pool() {
-    let staking_token_string: String = runtime::get_named_arg(ARG_NAME_STAKING_TOKEN);
-    let staking_token_hash = ContractHash::from_formatted_str(staking_token_string.as_str())
-        .expect("lock token hash string format is error");
-
-    let reward_token_string: String = runtime::get_named_arg(ARG_NAME_REWARD_TOKEN);
-    let reward_token_hash = ContractHash::from_formatted_str(reward_token_string.as_str())
-        .expect("lock token hash string format is error");
-
-    let start_time: u64 = runtime::get_named_arg(ARG_NAME_START_TIME);
-    let end_time: u64 = runtime::get_named_arg(ARG_NAME_END_TIME);
-    let precision: u64 = runtime::get_named_arg(ARG_NAME_PRECISION);
-    let total_reward: U256 = runtime::get_named_arg(ARG_NAME_TOTAL_REWARD);
-
-    StakeContract::default().add_pool(
-        staking_token_hash,
-        reward_token_hash,
-        start_time,
-        end_time,
-        precision,
-        total_reward,
-    );
-}
-
-#[no_mangle]
-pub extern "C" fn deposit() {
-    let amount: U256 = runtime::get_named_arg(ARG_NAME_AMOUNT);
-    let pool_id: u64 = runtime::get_named_arg(ARG_NAME_POOL_ID);
-
-    StakeContract::default().deposit(amount, pool_id);
-}
-
-#[no_mangle]
-pub extern "C" fn withdraw() {
-    let amount: U256 = runtime::get_named_arg(ARG_NAME_AMOUNT);
-    let pool_id: u64 = runtime::get_named_arg(ARG_NAME_POOL_ID);
-
-    StakeContract::default().withdraw(amount, pool_id);
-}
-
-#[no_mangle]
-pub extern "C" fn emergency_withdraw() {
-    let pool_id: u64 = runtime::get_named_arg(ARG_NAME_POOL_ID);
-
-    StakeContract::default().emergency_withdraw(pool_id);
-}
-
-#[no_mangle]
-pub extern "C" fn stop_reward() {
-    let pool_id: u64 = runtime::get_named_arg(ARG_NAME_POOL_ID);
-
-    StakeContract::default().stop_reward(pool_id);
-}
-
-#[no_mangle]
-pub extern "C" fn save_me() {
-    let token_hash_str: String = runtime::get_named_arg(ARG_NAME_TOKEN_HASH);
-    let token_hash = ContractHash::from_formatted_str(token_hash_str.as_str())
-        .expect("lock token hash string format is error");
-
-    let amount: U256 = runtime::get_named_arg(ARG_NAME_AMOUNT);
-
-    StakeContract::default().save_me(token_hash, amount);
-}
-
-#[no_mangle]
-pub extern "C" fn set_admin() {
-    let new_admin_hash: AccountHash = runtime::get_named_arg(ARG_NAME_NEW_ADMIN);
-
-    StakeContract::default().set_admin(new_admin_hash);
-}
-
-fn get_entry_points() -> EntryPoints {
-    let mut entry_points = EntryPoints::new();
-
-    entry_points.add_entry_point(EntryPoint::new(
-        ENTRYPOINT_NAME_INIT,
-        vec![Parameter::new(
-            ARG_NAME_ERC20_SELFCONTRACT_HASH,
-            String::cl_type(),
-        )],
-        CLType::I32,
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        ENTRYPOINT_NAME_ADD_POOL,
-        vec![
-            Parameter::new(ARG_NAME_STAKING_TOKEN, String::cl_type()),
-            Parameter::new(ARG_NAME_REWARD_TOKEN, String::cl_type()),
-            Parameter::new(ARG_NAME_START_TIME, u64::cl_type()),
-            Parameter::new(ARG_NAME_END_TIME, u64::cl_type()),
-            Parameter::new(ARG_NAME_PRECISION, u64::cl_type()),
-            Parameter::new(ARG_NAME_TOTAL_REWARD, U256::cl_type()),
-        ],
-        CLType::I32,
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        ENTRYPOINT_NAME_DEPOSIT,
-        vec![
-            Parameter::new(ARG_NAME_AMOUNT, U256::cl_type()),
-            Parameter::new(ARG_NAME_POOL_ID, u64::cl_type()),
-        ],
-        CLType::I32,
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        ENTRYPOINT_NAME_WITHDRAW,
-        vec![
-            Parameter::new(ARG_NAME_AMOUNT, U256::cl_type()),
-            Parameter::new(ARG_NAME_POOL_ID, u64::cl_type()),
-        ],
-        CLType::U256,
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        ENTRYPOINT_NAME_EMERGENCY_WITHDRAW,
-        vec![Parameter::new(ARG_NAME_POOL_ID, u64::cl_type())],
-        CLType::U64,
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        ENTRYPOINT_NAME_STOP_REWARD,
-        vec![Parameter::new(ARG_NAME_POOL_ID, u64::cl_type())],
-        CLType::U256,
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        ENTRYPOINT_NAME_SAVE_ME,
-        vec![
-            Parameter::new(ARG_NAME_TOKEN_HASH, String::cl_type()),
-            Parameter::new(ARG_NAME_AMOUNT, U256::cl_type()),
-        ],
-        CLType::U256,
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        ENTRYPOINT_NAME_SET_ADMIN,
-        vec![Parameter::new(ARG_NAME_NEW_ADMIN, AccountHash::cl_type())],
-        CLType::I32,
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points
-}
+#![no_std]
+#![no_main]
+#[macro_use]
+// #[cfg(not(target_arch = "wasm32"))]
+// compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+// We need to explicitly import the std alloc crate and `alloc::string::String` as we're in a
+// `no_std` environment.
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{FromBytes, ToBytes},
+    contracts::{EntryPoint, EntryPointAccess, EntryPointType, EntryPoints},
+    runtime_args, CLType, CLTyped, CLValue, ContractHash, ContractPackageHash, Key, Parameter,
+    RuntimeArgs, URef, U256,
+};
+
+use lock_staking::constants::{
+    ARG_NAME_ACCOUNT, ARG_NAME_AMOUNT, ARG_NAME_AMOUNTS, ARG_NAME_COOLDOWN, ARG_NAME_DESCRIPTION,
+    ARG_NAME_EMERGENCY, ARG_NAME_EMISSION_CURVE, ARG_NAME_END_TIME, ARG_NAME_EPOCHS,
+    ARG_NAME_ERC20_SELFCONTRACT_HASH, ARG_NAME_HALVING_PERIOD, ARG_NAME_INDEX, ARG_NAME_NAME,
+    ARG_NAME_NEW_ADMIN, ARG_NAME_POOL_ID, ARG_NAME_POOL_IDS, ARG_NAME_PRECISION, ARG_NAME_PURSE,
+    ARG_NAME_REWARD_PER_SECOND, ARG_NAME_REWARD_TOKEN, ARG_NAME_STAKING_TOKEN, ARG_NAME_START_TIME,
+    ARG_NAME_TIMESTAMP, ARG_NAME_TOKEN_HASH, ARG_NAME_TOTAL_REWARD, ARG_NAME_URL, CONTRACT_HASH,
+    CONTRACT_NAME, CONTRACT_VERSION, ENTRYPOINT_NAME_ADD_NATIVE_POOL, ENTRYPOINT_NAME_ADD_POOL,
+    ENTRYPOINT_NAME_ADD_RATE_POOL, ENTRYPOINT_NAME_COMPLETE_WITHDRAW,
+    ENTRYPOINT_NAME_CREATE_POOL_CONTRACT, ENTRYPOINT_NAME_DEPOSIT,
+    ENTRYPOINT_NAME_EMERGENCY_WITHDRAW, ENTRYPOINT_NAME_GET_POOL,
+    ENTRYPOINT_NAME_GET_POOL_CONTRACT, ENTRYPOINT_NAME_GET_POOL_CONTRACT_COUNT,
+    ENTRYPOINT_NAME_GET_POOL_METADATA, ENTRYPOINT_NAME_GET_USER_INFO, ENTRYPOINT_NAME_HARVEST_MANY,
+    ENTRYPOINT_NAME_INIT, ENTRYPOINT_NAME_MASS_UPDATE_POOLS, ENTRYPOINT_NAME_RECLAIM_UNALLOCATED,
+    ENTRYPOINT_NAME_REQUEST_WITHDRAW, ENTRYPOINT_NAME_SAVE_ME, ENTRYPOINT_NAME_SET_ADMIN,
+    ENTRYPOINT_NAME_SET_COOLDOWN, ENTRYPOINT_NAME_SET_EMERGENCY,
+    ENTRYPOINT_NAME_SET_EMISSION_CURVE, ENTRYPOINT_NAME_SET_POOL_METADATA,
+    ENTRYPOINT_NAME_STAKE_AT, ENTRYPOINT_NAME_STOP_REWARD, ENTRYPOINT_NAME_TOP_UP_POOL,
+    ENTRYPOINT_NAME_TOTAL_STAKED_AT, ENTRYPOINT_NAME_WITHDRAW, ENTRYPOINT_NAME_WITHDRAW_MANY,
+    KEY_NAME_INITIALIZED, KEY_NAME_REENTRANCY_LOCK, VESTOR_PACKAGE_NAME, VESTOR_UREF_NAME,
+};
+use lock_staking::{
+    constants::KEY_NAME_ADMIN, emission::EmissionCurve, utils, Address, PoolFactory, StakeContract,
+};
+
+// All the calls and their arg names are compiled into Entry Points for the runtime.
+// #[no_mangle] macro ensures that the function name will be the same string in WASM.
+#[no_mangle]
+pub extern "C" fn call() {
+    // The key shouldn't already exist in the named keys.
+    let counter_local_key = storage::new_uref(0_i32);
+
+    let mut vestor_named_keys: BTreeMap<String, Key> = BTreeMap::new();
+    let key_name = String::from(CONTRACT_NAME);
+    vestor_named_keys.insert(key_name, counter_local_key.into());
+
+    let admin_key = storage::new_uref(runtime::get_caller());
+    vestor_named_keys.insert(String::from(KEY_NAME_ADMIN), admin_key.into());
+
+    let initialized_key = storage::new_uref(false);
+    vestor_named_keys.insert(String::from(KEY_NAME_INITIALIZED), initialized_key.into());
+
+    let reentrancy_lock_key = storage::new_uref(false);
+    vestor_named_keys.insert(
+        String::from(KEY_NAME_REENTRANCY_LOCK),
+        reentrancy_lock_key.into(),
+    );
+
+    let (stored_contract_hash, contract_version) = storage::new_locked_contract(
+        get_factory_entry_points(),
+        Some(vestor_named_keys),
+        Some(String::from(VESTOR_PACKAGE_NAME)),
+        Some(String::from(VESTOR_UREF_NAME)),
+    );
+
+    let version_uref = storage::new_uref(contract_version);
+
+    runtime::put_key(CONTRACT_VERSION, version_uref.into());
+
+    runtime::put_key(CONTRACT_HASH, stored_contract_hash.into());
+}
+
+#[no_mangle]
+pub extern "C" fn init() {
+    let contract_package_hash: Key = runtime::get_named_arg(ARG_NAME_ERC20_SELFCONTRACT_HASH);
+    let hash = contract_package_hash.into_hash();
+    let p_hash = ContractPackageHash::new(hash.unwrap());
+    StakeContract::default().init(p_hash);
+}
+
+#[no_mangle]
+pub extern "C" fn add_pool() {
+    let staking_token_string: String = runtime::get_named_arg(ARG_NAME_STAKING_TOKEN);
+    let staking_token_hash = ContractHash::from_formatted_str(staking_token_string.as_str())
+        .expect("lock token hash string format is error");
+
+    let reward_token_string: String = runtime::get_named_arg(ARG_NAME_REWARD_TOKEN);
+    let reward_token_hash = ContractHash::from_formatted_str(reward_token_string.as_str())
+        .expect("lock token hash string format is error");
+
+    let start_time: u64 = runtime::get_named_arg(ARG_NAME_START_TIME);
+    let end_time: u64 = runtime::get_named_arg(ARG_NAME_END_TIME);
+    let precision: u64 = runtime::get_named_arg(ARG_NAME_PRECISION);
+    let total_reward: U256 = runtime::get_named_arg(ARG_NAME_TOTAL_REWARD);
+
+    let pool_id = StakeContract::default().add_pool(
+        staking_token_hash,
+        reward_token_hash,
+        start_time,
+        end_time,
+        precision,
+        total_reward,
+        get_optional_emission_curve(),
+    );
+
+    set_optional_pool_args(pool_id);
+
+    runtime::ret(CLValue::from_t(pool_id).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn add_native_pool() {
+    let staking_token_string: String = runtime::get_named_arg(ARG_NAME_STAKING_TOKEN);
+    let staking_token_hash = ContractHash::from_formatted_str(staking_token_string.as_str())
+        .expect("lock token hash string format is error");
+
+    let start_time: u64 = runtime::get_named_arg(ARG_NAME_START_TIME);
+    let end_time: u64 = runtime::get_named_arg(ARG_NAME_END_TIME);
+    let precision: u64 = runtime::get_named_arg(ARG_NAME_PRECISION);
+    let total_reward: U256 = runtime::get_named_arg(ARG_NAME_TOTAL_REWARD);
+    let purse: URef = runtime::get_named_arg(ARG_NAME_PURSE);
+
+    let pool_id = StakeContract::default().add_native_pool(
+        staking_token_hash,
+        start_time,
+        end_time,
+        precision,
+        total_reward,
+        purse,
+        get_optional_emission_curve(),
+    );
+
+    set_optional_pool_args(pool_id);
+
+    runtime::ret(CLValue::from_t(pool_id).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn add_rate_pool() {
+    let staking_token_string: String = runtime::get_named_arg(ARG_NAME_STAKING_TOKEN);
+    let staking_token_hash = ContractHash::from_formatted_str(staking_token_string.as_str())
+        .expect("lock token hash string format is error");
+
+    let reward_token_string: String = runtime::get_named_arg(ARG_NAME_REWARD_TOKEN);
+    let reward_token_hash = ContractHash::from_formatted_str(reward_token_string.as_str())
+        .expect("lock token hash string format is error");
+
+    let start_time: u64 = runtime::get_named_arg(ARG_NAME_START_TIME);
+    let precision: u64 = runtime::get_named_arg(ARG_NAME_PRECISION);
+    let reward_per_second: U256 = runtime::get_named_arg(ARG_NAME_REWARD_PER_SECOND);
+    let total_reward: U256 = runtime::get_named_arg(ARG_NAME_TOTAL_REWARD);
+
+    let pool_id = StakeContract::default().add_rate_pool(
+        staking_token_hash,
+        reward_token_hash,
+        start_time,
+        precision,
+        reward_per_second,
+        total_reward,
+    );
+
+    set_optional_pool_args(pool_id);
+
+    runtime::ret(CLValue::from_t(pool_id).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn top_up_pool() {
+    let pool_id: u64 = runtime::get_named_arg(ARG_NAME_POOL_ID);
+    let amount: U256 = runtime::get_named_arg(ARG_NAME_AMOUNT);
+
+    let received = StakeContract::default().top_up_pool(pool_id, amount);
+
+    runtime::ret(CLValue::from_t(received).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn request_withdraw() {
+    let amount: U256 = runtime::get_named_arg(ARG_NAME_AMOUNT);
+    let pool_id: u64 = runtime::get_named_arg(ARG_NAME_POOL_ID);
+
+    let release_time = StakeContract::default().request_withdraw(amount, pool_id);
+
+    runtime::ret(CLValue::from_t(release_time).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn complete_withdraw() {
+    let pool_id: u64 = runtime::get_named_arg(ARG_NAME_POOL_ID);
+
+    let amount = StakeContract::default().complete_withdraw(pool_id);
+
+    runtime::ret(CLValue::from_t(amount).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn set_cooldown() {
+    let pool_id: u64 = runtime::get_named_arg(ARG_NAME_POOL_ID);
+    let cooldown: u64 = runtime::get_named_arg(ARG_NAME_COOLDOWN);
+
+    StakeContract::default().set_cooldown(pool_id, cooldown);
+}
+
+#[no_mangle]
+pub extern "C" fn set_emergency() {
+    let pool_id: u64 = runtime::get_named_arg(ARG_NAME_POOL_ID);
+    let emergency: bool = runtime::get_named_arg(ARG_NAME_EMERGENCY);
+
+    StakeContract::default().set_emergency(pool_id, emergency);
+}
+
+// builds the curve of a new pool from its optional emission_curve,
+// epochs and halving_period args, linear if they are missing
+fn get_optional_emission_curve() -> EmissionCurve {
+    match utils::get_optional_named_arg::<u8>(ARG_NAME_EMISSION_CURVE) {
+        Some(emission_curve) => EmissionCurve::new(
+            emission_curve,
+            utils::get_optional_named_arg(ARG_NAME_EPOCHS).unwrap_or_default(),
+            utils::get_optional_named_arg(ARG_NAME_HALVING_PERIOD).unwrap_or_default(),
+        ),
+        None => EmissionCurve::Linear,
+    }
+}
+
+// applies the optional name, description, url and cooldown args of a new pool
+fn set_optional_pool_args(pool_id: u64) {
+    let name: Option<String> = utils::get_optional_named_arg(ARG_NAME_NAME);
+    let description: Option<String> = utils::get_optional_named_arg(ARG_NAME_DESCRIPTION);
+    let url: Option<String> = utils::get_optional_named_arg(ARG_NAME_URL);
+
+    if name.is_some() || description.is_some() || url.is_some() {
+        StakeContract::default().set_pool_metadata(
+            pool_id,
+            name.unwrap_or_default(),
+            description.unwrap_or_default(),
+            url.unwrap_or_default(),
+        );
+    }
+
+    if let Some(cooldown) = utils::get_optional_named_arg::<u64>(ARG_NAME_COOLDOWN) {
+        StakeContract::default().set_cooldown(pool_id, cooldown);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn deposit() {
+    let amount: U256 = runtime::get_named_arg(ARG_NAME_AMOUNT);
+    let pool_id: u64 = runtime::get_named_arg(ARG_NAME_POOL_ID);
+
+    let staked = StakeContract::default().deposit(amount, pool_id);
+
+    runtime::ret(CLValue::from_t(staked).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn withdraw() {
+    let amount: U256 = runtime::get_named_arg(ARG_NAME_AMOUNT);
+    let pool_id: u64 = runtime::get_named_arg(ARG_NAME_POOL_ID);
+
+    let withdrawn = StakeContract::default().withdraw(amount, pool_id);
+
+    runtime::ret(CLValue::from_t(withdrawn).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn emergency_withdraw() {
+    let pool_id: u64 = runtime::get_named_arg(ARG_NAME_POOL_ID);
+
+    let amount = StakeContract::default().emergency_withdraw(pool_id);
+
+    runtime::ret(CLValue::from_t(amount).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn stop_reward() {
+    let pool_id: u64 = runtime::get_named_arg(ARG_NAME_POOL_ID);
+
+    let amount = StakeContract::default().stop_reward(pool_id);
+
+    runtime::ret(CLValue::from_t(amount).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn save_me() {
+    let token_hash_str: String = runtime::get_named_arg(ARG_NAME_TOKEN_HASH);
+    let token_hash = ContractHash::from_formatted_str(token_hash_str.as_str())
+        .expect("lock token hash string format is error");
+
+    let amount: U256 = runtime::get_named_arg(ARG_NAME_AMOUNT);
+
+    let sent = StakeContract::default().save_me(token_hash, amount);
+
+    runtime::ret(CLValue::from_t(sent).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn set_admin() {
+    let new_admin_hash: AccountHash = runtime::get_named_arg(ARG_NAME_NEW_ADMIN);
+
+    StakeContract::default().set_admin(new_admin_hash);
+}
+
+#[no_mangle]
+pub extern "C" fn set_emission_curve() {
+    let pool_id: u64 = runtime::get_named_arg(ARG_NAME_POOL_ID);
+    let emission_curve: u8 = runtime::get_named_arg(ARG_NAME_EMISSION_CURVE);
+    let epochs: Vec<(u64, U256)> = runtime::get_named_arg(ARG_NAME_EPOCHS);
+    let halving_period: u64 = runtime::get_named_arg(ARG_NAME_HALVING_PERIOD);
+
+    StakeContract::default().set_emission_curve(
+        pool_id,
+        EmissionCurve::new(emission_curve, epochs, halving_period),
+    );
+}
+
+#[no_mangle]
+pub extern "C" fn reclaim_unallocated() {
+    let pool_id: u64 = runtime::get_named_arg(ARG_NAME_POOL_ID);
+
+    let amount = StakeContract::default().reclaim_unallocated(pool_id);
+
+    runtime::ret(CLValue::from_t(amount).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn harvest_many() {
+    let pool_ids: Vec<u64> = runtime::get_named_arg(ARG_NAME_POOL_IDS);
+
+    let rewards = StakeContract::default().harvest_many(pool_ids);
+
+    runtime::ret(CLValue::from_t(rewards).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn withdraw_many() {
+    let pool_ids: Vec<u64> = runtime::get_named_arg(ARG_NAME_POOL_IDS);
+    let amounts: Vec<U256> = runtime::get_named_arg(ARG_NAME_AMOUNTS);
+
+    let rewards = StakeContract::default().withdraw_many(pool_ids, amounts);
+
+    runtime::ret(CLValue::from_t(rewards).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn mass_update_pools() {
+    let pool_ids: Vec<u64> = runtime::get_named_arg(ARG_NAME_POOL_IDS);
+
+    StakeContract::default().mass_update_pools(pool_ids);
+}
+
+#[no_mangle]
+pub extern "C" fn stake_at() {
+    let account: Address = runtime::get_named_arg(ARG_NAME_ACCOUNT);
+    let pool_id: u64 = runtime::get_named_arg(ARG_NAME_POOL_ID);
+    let timestamp: u64 = runtime::get_named_arg(ARG_NAME_TIMESTAMP);
+
+    let amount = StakeContract::default().stake_at(account, pool_id, timestamp);
+
+    runtime::ret(CLValue::from_t(amount).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn total_staked_at() {
+    let pool_id: u64 = runtime::get_named_arg(ARG_NAME_POOL_ID);
+    let timestamp: u64 = runtime::get_named_arg(ARG_NAME_TIMESTAMP);
+
+    let amount = StakeContract::default().total_staked_at(pool_id, timestamp);
+
+    runtime::ret(CLValue::from_t(amount).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn create_pool_contract() {
+    let reward_token_string: String = runtime::get_named_arg(ARG_NAME_REWARD_TOKEN);
+    let reward_token_hash = ContractHash::from_formatted_str(reward_token_string.as_str())
+        .expect("lock token hash string format is error");
+
+    let total_reward: U256 = runtime::get_named_arg(ARG_NAME_TOTAL_REWARD);
+
+    let mut pool_args = runtime_args! {
+        ARG_NAME_STAKING_TOKEN => runtime::get_named_arg::<String>(ARG_NAME_STAKING_TOKEN),
+        ARG_NAME_REWARD_TOKEN => reward_token_string,
+        ARG_NAME_START_TIME => runtime::get_named_arg::<u64>(ARG_NAME_START_TIME),
+        ARG_NAME_PRECISION => runtime::get_named_arg::<u64>(ARG_NAME_PRECISION)
+    };
+
+    // a reward_per_second arg makes it a rate pool, which has no end_time
+    let add_pool_entry_point =
+        match utils::get_optional_named_arg::<U256>(ARG_NAME_REWARD_PER_SECOND) {
+            Some(reward_per_second) => {
+                pool_args
+                    .insert(ARG_NAME_REWARD_PER_SECOND, reward_per_second)
+                    .unwrap_or_revert();
+                ENTRYPOINT_NAME_ADD_RATE_POOL
+            }
+            None => {
+                pool_args
+                    .insert(
+                        ARG_NAME_END_TIME,
+                        runtime::get_named_arg::<u64>(ARG_NAME_END_TIME),
+                    )
+                    .unwrap_or_revert();
+                forward_optional_arg::<u8>(&mut pool_args, ARG_NAME_EMISSION_CURVE);
+                forward_optional_arg::<Vec<(u64, U256)>>(&mut pool_args, ARG_NAME_EPOCHS);
+                forward_optional_arg::<u64>(&mut pool_args, ARG_NAME_HALVING_PERIOD);
+                ENTRYPOINT_NAME_ADD_POOL
+            }
+        };
+
+    forward_optional_arg::<String>(&mut pool_args, ARG_NAME_NAME);
+    forward_optional_arg::<String>(&mut pool_args, ARG_NAME_DESCRIPTION);
+    forward_optional_arg::<String>(&mut pool_args, ARG_NAME_URL);
+    forward_optional_arg::<u64>(&mut pool_args, ARG_NAME_COOLDOWN);
+
+    let hashes = PoolFactory::default().create_pool_contract(
+        get_entry_points(),
+        add_pool_entry_point,
+        reward_token_hash,
+        total_reward,
+        pool_args,
+    );
+
+    runtime::ret(CLValue::from_t(hashes).unwrap_or_revert())
+}
+
+// copies an optional arg of this call into args if it was passed
+fn forward_optional_arg<T: CLTyped + FromBytes + ToBytes>(args: &mut RuntimeArgs, name: &str) {
+    if let Some(value) = utils::get_optional_named_arg::<T>(name) {
+        args.insert(name, value).unwrap_or_revert();
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn get_pool_contract_count() {
+    let count = PoolFactory::default().get_pool_contract_count();
+
+    runtime::ret(CLValue::from_t(count).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn get_pool_contract() {
+    let index: u64 = runtime::get_named_arg(ARG_NAME_INDEX);
+
+    let hashes = PoolFactory::default().get_pool_contract(index);
+
+    runtime::ret(CLValue::from_t(hashes).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn get_pool() {
+    let pool_id: u64 = runtime::get_named_arg(ARG_NAME_POOL_ID);
+
+    let pool = StakeContract::default().get_pool(pool_id);
+
+    runtime::ret(CLValue::from_t(pool).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn get_user_info() {
+    let pool_id: u64 = runtime::get_named_arg(ARG_NAME_POOL_ID);
+    let account: Address = runtime::get_named_arg(ARG_NAME_ACCOUNT);
+
+    let user_info = StakeContract::default().get_user_info(pool_id, account);
+
+    runtime::ret(CLValue::from_t(user_info).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn set_pool_metadata() {
+    let pool_id: u64 = runtime::get_named_arg(ARG_NAME_POOL_ID);
+    let name: String = runtime::get_named_arg(ARG_NAME_NAME);
+    let description: String = runtime::get_named_arg(ARG_NAME_DESCRIPTION);
+    let url: String = runtime::get_named_arg(ARG_NAME_URL);
+
+    StakeContract::default().set_pool_metadata(pool_id, name, description, url);
+}
+
+#[no_mangle]
+pub extern "C" fn get_pool_metadata() {
+    let pool_id: u64 = runtime::get_named_arg(ARG_NAME_POOL_ID);
+
+    let metadata = StakeContract::default().get_pool_metadata(pool_id);
+
+    runtime::ret(CLValue::from_t(metadata).unwrap_or_revert())
+}
+
+// entry points of the installed contract, a staking contract
+// that can also install a separate contract per pool
+fn get_factory_entry_points() -> EntryPoints {
+    let mut entry_points = get_entry_points();
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_CREATE_POOL_CONTRACT,
+        vec![
+            Parameter::new(ARG_NAME_STAKING_TOKEN, String::cl_type()),
+            Parameter::new(ARG_NAME_REWARD_TOKEN, String::cl_type()),
+            Parameter::new(ARG_NAME_START_TIME, u64::cl_type()),
+            Parameter::new(ARG_NAME_END_TIME, u64::cl_type()),
+            Parameter::new(ARG_NAME_PRECISION, u64::cl_type()),
+            Parameter::new(ARG_NAME_TOTAL_REWARD, U256::cl_type()),
+            Parameter::new(ARG_NAME_REWARD_PER_SECOND, U256::cl_type()),
+            Parameter::new(ARG_NAME_NAME, String::cl_type()),
+            Parameter::new(ARG_NAME_DESCRIPTION, String::cl_type()),
+            Parameter::new(ARG_NAME_URL, String::cl_type()),
+            Parameter::new(ARG_NAME_COOLDOWN, u64::cl_type()),
+            Parameter::new(ARG_NAME_EMISSION_CURVE, u8::cl_type()),
+            Parameter::new(ARG_NAME_EPOCHS, Vec::<(u64, U256)>::cl_type()),
+            Parameter::new(ARG_NAME_HALVING_PERIOD, u64::cl_type()),
+        ],
+        <(ContractHash, ContractPackageHash)>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_GET_POOL_CONTRACT_COUNT,
+        vec![],
+        CLType::U64,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_GET_POOL_CONTRACT,
+        vec![Parameter::new(ARG_NAME_INDEX, u64::cl_type())],
+        <(ContractHash, ContractPackageHash)>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points
+}
+
+// entry points of every staking contract
+fn get_entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_INIT,
+        vec![Parameter::new(
+            ARG_NAME_ERC20_SELFCONTRACT_HASH,
+            String::cl_type(),
+        )],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_ADD_POOL,
+        vec![
+            Parameter::new(ARG_NAME_STAKING_TOKEN, String::cl_type()),
+            Parameter::new(ARG_NAME_REWARD_TOKEN, String::cl_type()),
+            Parameter::new(ARG_NAME_START_TIME, u64::cl_type()),
+            Parameter::new(ARG_NAME_END_TIME, u64::cl_type()),
+            Parameter::new(ARG_NAME_PRECISION, u64::cl_type()),
+            Parameter::new(ARG_NAME_TOTAL_REWARD, U256::cl_type()),
+            Parameter::new(ARG_NAME_NAME, String::cl_type()),
+            Parameter::new(ARG_NAME_DESCRIPTION, String::cl_type()),
+            Parameter::new(ARG_NAME_URL, String::cl_type()),
+            Parameter::new(ARG_NAME_COOLDOWN, u64::cl_type()),
+            Parameter::new(ARG_NAME_EMISSION_CURVE, u8::cl_type()),
+            Parameter::new(ARG_NAME_EPOCHS, Vec::<(u64, U256)>::cl_type()),
+            Parameter::new(ARG_NAME_HALVING_PERIOD, u64::cl_type()),
+        ],
+        CLType::U64,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_ADD_NATIVE_POOL,
+        vec![
+            Parameter::new(ARG_NAME_STAKING_TOKEN, String::cl_type()),
+            Parameter::new(ARG_NAME_START_TIME, u64::cl_type()),
+            Parameter::new(ARG_NAME_END_TIME, u64::cl_type()),
+            Parameter::new(ARG_NAME_PRECISION, u64::cl_type()),
+            Parameter::new(ARG_NAME_TOTAL_REWARD, U256::cl_type()),
+            Parameter::new(ARG_NAME_PURSE, URef::cl_type()),
+            Parameter::new(ARG_NAME_NAME, String::cl_type()),
+            Parameter::new(ARG_NAME_DESCRIPTION, String::cl_type()),
+            Parameter::new(ARG_NAME_URL, String::cl_type()),
+            Parameter::new(ARG_NAME_COOLDOWN, u64::cl_type()),
+            Parameter::new(ARG_NAME_EMISSION_CURVE, u8::cl_type()),
+            Parameter::new(ARG_NAME_EPOCHS, Vec::<(u64, U256)>::cl_type()),
+            Parameter::new(ARG_NAME_HALVING_PERIOD, u64::cl_type()),
+        ],
+        CLType::U64,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_DEPOSIT,
+        vec![
+            Parameter::new(ARG_NAME_AMOUNT, U256::cl_type()),
+            Parameter::new(ARG_NAME_POOL_ID, u64::cl_type()),
+        ],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_WITHDRAW,
+        vec![
+            Parameter::new(ARG_NAME_AMOUNT, U256::cl_type()),
+            Parameter::new(ARG_NAME_POOL_ID, u64::cl_type()),
+        ],
+        <(U256, U256)>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_EMERGENCY_WITHDRAW,
+        vec![Parameter::new(ARG_NAME_POOL_ID, u64::cl_type())],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_STOP_REWARD,
+        vec![Parameter::new(ARG_NAME_POOL_ID, u64::cl_type())],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_SAVE_ME,
+        vec![
+            Parameter::new(ARG_NAME_TOKEN_HASH, String::cl_type()),
+            Parameter::new(ARG_NAME_AMOUNT, U256::cl_type()),
+        ],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_SET_ADMIN,
+        vec![Parameter::new(ARG_NAME_NEW_ADMIN, AccountHash::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_RECLAIM_UNALLOCATED,
+        vec![Parameter::new(ARG_NAME_POOL_ID, u64::cl_type())],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_SET_EMISSION_CURVE,
+        vec![
+            Parameter::new(ARG_NAME_POOL_ID, u64::cl_type()),
+            Parameter::new(ARG_NAME_EMISSION_CURVE, u8::cl_type()),
+            Parameter::new(ARG_NAME_EPOCHS, Vec::<(u64, U256)>::cl_type()),
+            Parameter::new(ARG_NAME_HALVING_PERIOD, u64::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_HARVEST_MANY,
+        vec![Parameter::new(ARG_NAME_POOL_IDS, Vec::<u64>::cl_type())],
+        Vec::<U256>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_WITHDRAW_MANY,
+        vec![
+            Parameter::new(ARG_NAME_POOL_IDS, Vec::<u64>::cl_type()),
+            Parameter::new(ARG_NAME_AMOUNTS, Vec::<U256>::cl_type()),
+        ],
+        Vec::<U256>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_MASS_UPDATE_POOLS,
+        vec![Parameter::new(ARG_NAME_POOL_IDS, Vec::<u64>::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_STAKE_AT,
+        vec![
+            Parameter::new(ARG_NAME_ACCOUNT, Key::cl_type()),
+            Parameter::new(ARG_NAME_POOL_ID, u64::cl_type()),
+            Parameter::new(ARG_NAME_TIMESTAMP, u64::cl_type()),
+        ],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_TOTAL_STAKED_AT,
+        vec![
+            Parameter::new(ARG_NAME_POOL_ID, u64::cl_type()),
+            Parameter::new(ARG_NAME_TIMESTAMP, u64::cl_type()),
+        ],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_GET_POOL,
+        vec![Parameter::new(ARG_NAME_POOL_ID, u64::cl_type())],
+        <(BTreeMap<String, Key>, BTreeMap<String, U256>)>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_GET_USER_INFO,
+        vec![
+            Parameter::new(ARG_NAME_POOL_ID, u64::cl_type()),
+            Parameter::new(ARG_NAME_ACCOUNT, Key::cl_type()),
+        ],
+        BTreeMap::<String, U256>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_SET_POOL_METADATA,
+        vec![
+            Parameter::new(ARG_NAME_POOL_ID, u64::cl_type()),
+            Parameter::new(ARG_NAME_NAME, String::cl_type()),
+            Parameter::new(ARG_NAME_DESCRIPTION, String::cl_type()),
+            Parameter::new(ARG_NAME_URL, String::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_GET_POOL_METADATA,
+        vec![Parameter::new(ARG_NAME_POOL_ID, u64::cl_type())],
+        BTreeMap::<String, String>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_ADD_RATE_POOL,
+        vec![
+            Parameter::new(ARG_NAME_STAKING_TOKEN, String::cl_type()),
+            Parameter::new(ARG_NAME_REWARD_TOKEN, String::cl_type()),
+            Parameter::new(ARG_NAME_START_TIME, u64::cl_type()),
+            Parameter::new(ARG_NAME_PRECISION, u64::cl_type()),
+            Parameter::new(ARG_NAME_REWARD_PER_SECOND, U256::cl_type()),
+            Parameter::new(ARG_NAME_TOTAL_REWARD, U256::cl_type()),
+            Parameter::new(ARG_NAME_NAME, String::cl_type()),
+            Parameter::new(ARG_NAME_DESCRIPTION, String::cl_type()),
+            Parameter::new(ARG_NAME_URL, String::cl_type()),
+            Parameter::new(ARG_NAME_COOLDOWN, u64::cl_type()),
+        ],
+        CLType::U64,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_TOP_UP_POOL,
+        vec![
+            Parameter::new(ARG_NAME_POOL_ID, u64::cl_type()),
+            Parameter::new(ARG_NAME_AMOUNT, U256::cl_type()),
+        ],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_REQUEST_WITHDRAW,
+        vec![
+            Parameter::new(ARG_NAME_AMOUNT, U256::cl_type()),
+            Parameter::new(ARG_NAME_POOL_ID, u64::cl_type()),
+        ],
+        CLType::U64,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_COMPLETE_WITHDRAW,
+        vec![Parameter::new(ARG_NAME_POOL_ID, u64::cl_type())],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_SET_COOLDOWN,
+        vec![
+            Parameter::new(ARG_NAME_POOL_ID, u64::cl_type()),
+            Parameter::new(ARG_NAME_COOLDOWN, u64::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRYPOINT_NAME_SET_EMERGENCY,
+        vec![
+            Parameter::new(ARG_NAME_POOL_ID, u64::cl_type()),
+            Parameter::new(ARG_NAME_EMERGENCY, bool::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points
+}
@@ -0,0 +1,71 @@
+//! Overflow-safe reward accrual math for stake pools.
+//!
+//! Products are widened to `U512` before dividing, so they only fail
+//! when the final result doesn't fit into a `U256`.  Every operation
+//! returns `Error::Overflow` or `Error::Underflow` instead of trapping.
+use casper_types::{U256, U512};
+
+use crate::{utils::u256_to_u512, Error};
+
+// 10^precision, the scaling factor of acc_token_per_share
+pub fn precision_factor(precision: u64) -> Result<U256, Error> {
+    U256::from(10)
+        .checked_pow(U256::from(precision))
+        .ok_or(Error::Overflow)
+}
+
+// a * b / c without overflowing on the intermediate product
+pub fn mul_div(a: U256, b: U256, c: U256) -> Result<U256, Error> {
+    if c.is_zero() {
+        return Err(Error::Overflow);
+    }
+
+    u512_to_u256(u256_to_u512(a) * u256_to_u512(b) / u256_to_u512(c))
+}
+
+// rewards a stake has accrued since the pool started:
+// amount * acc_token_per_share / 10^precision
+pub fn accrued(amount: U256, acc_token_per_share: U256, precision: u64) -> Result<U256, Error> {
+    mul_div(amount, acc_token_per_share, precision_factor(precision)?)
+}
+
+// rewards a stake can claim, its accrued rewards minus its reward_debt
+pub fn pending(
+    amount: U256,
+    acc_token_per_share: U256,
+    precision: u64,
+    reward_debt: U256,
+) -> Result<U256, Error> {
+    sub(
+        accrued(amount, acc_token_per_share, precision)?,
+        reward_debt,
+    )
+}
+
+// growth of acc_token_per_share when rewards are spread over total_staked:
+// rewards * 10^precision / total_staked
+pub fn acc_increment(rewards: U256, precision: u64, total_staked: U256) -> Result<U256, Error> {
+    mul_div(rewards, precision_factor(precision)?, total_staked)
+}
+
+pub fn mul(a: U256, b: U256) -> Result<U256, Error> {
+    a.checked_mul(b).ok_or(Error::Overflow)
+}
+
+pub fn add(a: U256, b: U256) -> Result<U256, Error> {
+    a.checked_add(b).ok_or(Error::Overflow)
+}
+
+pub fn sub(a: U256, b: U256) -> Result<U256, Error> {
+    a.checked_sub(b).ok_or(Error::Underflow)
+}
+
+pub fn u512_to_u256(value: U512) -> Result<U256, Error> {
+    if value.bits() > 256 {
+        return Err(Error::Overflow);
+    }
+
+    let mut bytes = [0_u8; 64];
+    value.to_little_endian(&mut bytes);
+    Ok(U256::from_little_endian(&bytes[..32]))
+}
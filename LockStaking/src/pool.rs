@@ -1,252 +1,416 @@
-extern crate alloc;
-
-use core::convert::TryInto;
-
-use alloc::vec::Vec;
-
-use casper_contract::unwrap_or_revert::UnwrapOrRevert;
-
-use casper_types::{account::AccountHash, bytesrepr::ToBytes, ContractHash, U256};
-
-#[derive(Clone, Copy)]
-pub struct UserInfo {
-    // 32
-    pub amount: U256,
-    // 32 : 64
-    pub reward_debt: U256,
-}
-
-impl UserInfo {
-    pub fn pack(&self) -> Vec<u8> {
-        let mut amount_bytes = [0_u8; 64];
-        self.amount.to_little_endian(&mut amount_bytes[..32]);
-
-        let mut reward_debt_bytes = [0_u8; 64];
-        self.reward_debt
-            .to_little_endian(&mut reward_debt_bytes[..32]);
-
-        let mut res: Vec<u8> = Vec::new();
-
-        for i in amount_bytes.iter().take(32) {
-            res.push(*i)
-        }
-
-        for i in 32..64 {
-            res.push(reward_debt_bytes[i - 32])
-        }
-
-        res
-    }
-
-    pub fn unpack(src: Vec<u8>) -> Self {
-        let amount = U256::from_little_endian(&src[0..32]);
-        let reward_debt = U256::from_little_endian(&src[32..64]);
-
-        Self {
-            amount,
-            reward_debt,
-        }
-    }
-
-    pub fn default() -> Self {
-        Self {
-            amount: U256::zero(),
-            reward_debt: U256::zero(),
-        }
-    }
-}
-
-pub struct StakePool {
-    // 8 : 8
-    pub id: u64,
-    // 8 : 16,
-    pub last_reward_timestamp: u64,
-    // 32 : 48
-    pub staking_token: ContractHash,
-    // 32 : 80
-    pub reward_token: ContractHash,
-    // 8  : 88
-    pub start_time: u64,
-    // 8  : 96
-    pub end_time: u64,
-    // 8  : 104
-    pub precision: u64,
-    // 32 : 136
-    pub owner: AccountHash,
-    // 32 : 168
-    pub total_reward: U256,
-    // 32 : 200
-    pub acc_token_per_share: U256,
-    // 32 : 232
-    pub total_staked: U256,
-}
-
-impl StakePool {
-    // returns all lock info as a byte array
-    pub fn pack(&self) -> Vec<u8> {
-        let id_bytes = self.id.into_bytes().unwrap_or_revert();
-        let last_reward_timestamp = self.last_reward_timestamp.into_bytes().unwrap_or_revert();
-        let staking_token_bytes = self.staking_token.as_bytes();
-        let reward_token_bytes = self.reward_token.as_bytes();
-        let start_time_bytes = self.start_time.into_bytes().unwrap_or_revert();
-        let end_time_bytes = self.end_time.into_bytes().unwrap_or_revert();
-        let precision_bytes = self.precision.into_bytes().unwrap_or_revert();
-        let owner_bytes = self.owner.as_bytes();
-
-        let mut total_reward_bytes = [0_u8; 64];
-        self.total_reward
-            .to_little_endian(&mut total_reward_bytes[..32]);
-
-        let mut acc_token_per_share_bytes = [0_u8; 64];
-        self.acc_token_per_share
-            .to_little_endian(&mut acc_token_per_share_bytes[..32]);
-
-        let mut total_staked_bytes = [0_u8; 64];
-        self.total_staked
-            .to_little_endian(&mut total_staked_bytes[..32]);
-
-        let mut res: Vec<u8> = Vec::new();
-
-        for i in id_bytes {
-            res.push(i)
-        }
-
-        for i in 8..16 {
-            res.push(last_reward_timestamp[i - 8])
-        }
-
-        for i in 16..48 {
-            res.push(staking_token_bytes[i - 16])
-        }
-
-        for i in 48..80 {
-            res.push(reward_token_bytes[i - 48])
-        }
-
-        for i in 80..88 {
-            res.push(start_time_bytes[i - 80])
-        }
-
-        for i in 88..96 {
-            res.push(end_time_bytes[i - 88])
-        }
-
-        for i in 96..104 {
-            res.push(precision_bytes[i - 96])
-        }
-
-        for i in 104..136 {
-            res.push(owner_bytes[i - 104])
-        }
-
-        for i in 136..168 {
-            res.push(total_reward_bytes[i - 136])
-        }
-
-        for i in 168..200 {
-            res.push(acc_token_per_share_bytes[i - 168])
-        }
-
-        for i in 200..232 {
-            res.push(total_staked_bytes[i - 200])
-        }
-        res
-    }
-
-    pub fn unpack(src: Vec<u8>) -> Self {
-        let id = u64::from_le_bytes(src[0..8].try_into().unwrap());
-        let last_reward_timestamp = u64::from_le_bytes(src[8..16].try_into().unwrap());
-        let staking_token: ContractHash = src[16..48].try_into().unwrap();
-        let reward_token: ContractHash = src[48..80].try_into().unwrap();
-
-        let start_time = u64::from_le_bytes(src[80..88].try_into().unwrap());
-        let end_time = u64::from_le_bytes(src[88..96].try_into().unwrap());
-        let precision = u64::from_le_bytes(src[96..104].try_into().unwrap());
-
-        let owner: AccountHash = src[104..136].try_into().unwrap();
-        let total_reward = U256::from_little_endian(&src[136..168]);
-        let acc_token_per_share = U256::from_little_endian(&src[168..200]);
-        let total_staked = U256::from_little_endian(&src[200..232]);
-
-        Self {
-            id,
-            last_reward_timestamp,
-            staking_token,
-            reward_token,
-            start_time,
-            end_time,
-            precision,
-            owner,
-            total_reward,
-            acc_token_per_share,
-            total_staked,
-        }
-    }
-
-    // gets the owner account from StakePool
-    // used in caller_is_recipient() function
-    pub fn unpack_owner(src: &[u8]) -> [u8; 32] {
-        let owner: [u8; 32] = src[104..136].try_into().unwrap();
-        owner
-    }
-}
-
-pub enum DepositScenario {
-    PendingZero,
-    EqualTokenPendingGreater,
-    EqualTokenPendingLesser,
-    ClaimRewardAndDeposit,
-}
-
-impl DepositScenario {
-    pub fn get_scenario(
-        pending: U256,
-        amount: U256,
-        reward_token: ContractHash,
-        staking_token: ContractHash,
-    ) -> Self {
-        if pending.is_zero() {
-            return Self::PendingZero;
-        }
-
-        let pending_gt_amount: bool = pending > amount;
-
-        let reward_token_eq_staking_token: bool = reward_token == staking_token;
-
-        if pending_gt_amount && reward_token_eq_staking_token {
-            return Self::EqualTokenPendingGreater;
-        }
-
-        if !pending_gt_amount && reward_token_eq_staking_token {
-            return Self::EqualTokenPendingLesser;
-        }
-
-        Self::ClaimRewardAndDeposit
-    }
-}
-
-pub enum WithdrawScenario {
-    PendingZero,
-    EqualTokens,
-    DifferentTokens,
-}
-
-impl WithdrawScenario {
-    pub fn get_scenario(
-        pending: U256,
-        reward_token: ContractHash,
-        staking_token: ContractHash,
-    ) -> Self {
-        if pending.is_zero() {
-            return Self::PendingZero;
-        }
-
-        let reward_token_eq_staking_token: bool = reward_token == staking_token;
-
-        if reward_token_eq_staking_token {
-            return Self::EqualTokens;
-        }
-
-        Self::DifferentTokens
-    }
-}
+extern crate alloc;
+
+use core::convert::TryInto;
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+
+use casper_types::{account::AccountHash, bytesrepr::ToBytes, ContractHash, Key, U256};
+
+use crate::emission::EmissionCurve;
+
+#[derive(Clone, Copy)]
+pub struct UserInfo {
+    // 32
+    pub amount: U256,
+    // 32 : 64
+    pub reward_debt: U256,
+}
+
+impl UserInfo {
+    pub fn pack(&self) -> Vec<u8> {
+        let mut amount_bytes = [0_u8; 64];
+        self.amount.to_little_endian(&mut amount_bytes[..32]);
+
+        let mut reward_debt_bytes = [0_u8; 64];
+        self.reward_debt
+            .to_little_endian(&mut reward_debt_bytes[..32]);
+
+        let mut res: Vec<u8> = Vec::new();
+
+        for i in amount_bytes.iter().take(32) {
+            res.push(*i)
+        }
+
+        for i in 32..64 {
+            res.push(reward_debt_bytes[i - 32])
+        }
+
+        res
+    }
+
+    pub fn unpack(src: Vec<u8>) -> Self {
+        let amount = U256::from_little_endian(&src[0..32]);
+        let reward_debt = U256::from_little_endian(&src[32..64]);
+
+        Self {
+            amount,
+            reward_debt,
+        }
+    }
+
+    pub fn default() -> Self {
+        Self {
+            amount: U256::zero(),
+            reward_debt: U256::zero(),
+        }
+    }
+
+    // returns user info by field name for the get_user_info entry point
+    pub fn to_named_values(&self) -> BTreeMap<String, U256> {
+        let mut values = BTreeMap::new();
+
+        values.insert(String::from("amount"), self.amount);
+        values.insert(String::from("reward_debt"), self.reward_debt);
+
+        values
+    }
+}
+
+// stake waiting out a pool's cooldown
+#[derive(Clone, Copy)]
+pub struct Unbond {
+    // 32
+    pub amount: U256,
+    // 8 : 40
+    pub release_time: u64,
+}
+
+impl Unbond {
+    pub fn pack(&self) -> Vec<u8> {
+        let mut res = [0_u8; 40];
+
+        self.amount.to_little_endian(&mut res[..32]);
+        res[32..40].copy_from_slice(&self.release_time.to_le_bytes());
+
+        res.to_vec()
+    }
+
+    pub fn unpack(src: Vec<u8>) -> Self {
+        Self {
+            amount: U256::from_little_endian(&src[0..32]),
+            release_time: u64::from_le_bytes(src[32..40].try_into().unwrap()),
+        }
+    }
+
+    pub fn default() -> Self {
+        Self {
+            amount: U256::zero(),
+            release_time: 0u64,
+        }
+    }
+}
+
+pub struct StakePool {
+    // 8 : 8
+    pub id: u64,
+    // 8 : 16,
+    pub last_reward_timestamp: u64,
+    // 32 : 48
+    pub staking_token: ContractHash,
+    // 32 : 80
+    pub reward_token: ContractHash,
+    // 8  : 88
+    pub start_time: u64,
+    // 8  : 96
+    pub end_time: u64,
+    // 8  : 104
+    pub precision: u64,
+    // 32 : 136
+    pub owner: AccountHash,
+    // 32 : 168
+    pub total_reward: U256,
+    // 32 : 200
+    pub acc_token_per_share: U256,
+    // 32 : 232
+    pub total_staked: U256,
+    // 1  : 233
+    pub native_reward: bool,
+    // 32 : 265
+    pub unallocated_reward: U256,
+    // 32 : 297
+    pub emitted_reward: U256,
+    // 32 : 329
+    pub reward_per_second: U256,
+    // 8  : 337
+    pub cooldown: u64,
+    // 1  : 338
+    pub emergency: bool,
+    // 1 + params : (338 + 1 + params)
+    pub emission: EmissionCurve,
+}
+
+impl StakePool {
+    // returns pool info by field name for the get_pool entry point,
+    // hashes as keys and all other fields as numbers
+    pub fn to_named_values(&self) -> (BTreeMap<String, Key>, BTreeMap<String, U256>) {
+        let mut keys = BTreeMap::new();
+
+        keys.insert(
+            String::from("staking_token"),
+            Key::Hash(self.staking_token.value()),
+        );
+        keys.insert(
+            String::from("reward_token"),
+            Key::Hash(self.reward_token.value()),
+        );
+        keys.insert(String::from("owner"), Key::Account(self.owner));
+
+        let mut values = BTreeMap::new();
+
+        values.insert(String::from("id"), U256::from(self.id));
+        values.insert(
+            String::from("last_reward_timestamp"),
+            U256::from(self.last_reward_timestamp),
+        );
+        values.insert(String::from("start_time"), U256::from(self.start_time));
+        values.insert(String::from("end_time"), U256::from(self.end_time));
+        values.insert(String::from("precision"), U256::from(self.precision));
+        values.insert(String::from("total_reward"), self.total_reward);
+        values.insert(
+            String::from("acc_token_per_share"),
+            self.acc_token_per_share,
+        );
+        values.insert(String::from("total_staked"), self.total_staked);
+        values.insert(
+            String::from("native_reward"),
+            U256::from(u8::from(self.native_reward)),
+        );
+        values.insert(String::from("unallocated_reward"), self.unallocated_reward);
+        values.insert(String::from("emitted_reward"), self.emitted_reward);
+        values.insert(String::from("reward_per_second"), self.reward_per_second);
+        values.insert(String::from("cooldown"), U256::from(self.cooldown));
+        values.insert(
+            String::from("emergency"),
+            U256::from(u8::from(self.emergency)),
+        );
+        values.insert(
+            String::from("emission_curve"),
+            U256::from(self.emission.kind()),
+        );
+
+        (keys, values)
+    }
+
+    // returns all lock info as a byte array
+    pub fn pack(&self) -> Vec<u8> {
+        let id_bytes = self.id.into_bytes().unwrap_or_revert();
+        let last_reward_timestamp = self.last_reward_timestamp.into_bytes().unwrap_or_revert();
+        let staking_token_bytes = self.staking_token.as_bytes();
+        let reward_token_bytes = self.reward_token.as_bytes();
+        let start_time_bytes = self.start_time.into_bytes().unwrap_or_revert();
+        let end_time_bytes = self.end_time.into_bytes().unwrap_or_revert();
+        let precision_bytes = self.precision.into_bytes().unwrap_or_revert();
+        let owner_bytes = self.owner.as_bytes();
+
+        let mut total_reward_bytes = [0_u8; 64];
+        self.total_reward
+            .to_little_endian(&mut total_reward_bytes[..32]);
+
+        let mut acc_token_per_share_bytes = [0_u8; 64];
+        self.acc_token_per_share
+            .to_little_endian(&mut acc_token_per_share_bytes[..32]);
+
+        let mut total_staked_bytes = [0_u8; 64];
+        self.total_staked
+            .to_little_endian(&mut total_staked_bytes[..32]);
+
+        let mut res: Vec<u8> = Vec::new();
+
+        for i in id_bytes {
+            res.push(i)
+        }
+
+        for i in 8..16 {
+            res.push(last_reward_timestamp[i - 8])
+        }
+
+        for i in 16..48 {
+            res.push(staking_token_bytes[i - 16])
+        }
+
+        for i in 48..80 {
+            res.push(reward_token_bytes[i - 48])
+        }
+
+        for i in 80..88 {
+            res.push(start_time_bytes[i - 80])
+        }
+
+        for i in 88..96 {
+            res.push(end_time_bytes[i - 88])
+        }
+
+        for i in 96..104 {
+            res.push(precision_bytes[i - 96])
+        }
+
+        for i in 104..136 {
+            res.push(owner_bytes[i - 104])
+        }
+
+        for i in 136..168 {
+            res.push(total_reward_bytes[i - 136])
+        }
+
+        for i in 168..200 {
+            res.push(acc_token_per_share_bytes[i - 168])
+        }
+
+        for i in 200..232 {
+            res.push(total_staked_bytes[i - 200])
+        }
+
+        res.push(self.native_reward as u8);
+
+        let mut unallocated_reward_bytes = [0_u8; 64];
+        self.unallocated_reward
+            .to_little_endian(&mut unallocated_reward_bytes[..32]);
+
+        for i in 233..265 {
+            res.push(unallocated_reward_bytes[i - 233])
+        }
+
+        let mut emitted_reward_bytes = [0_u8; 64];
+        self.emitted_reward
+            .to_little_endian(&mut emitted_reward_bytes[..32]);
+
+        for i in 265..297 {
+            res.push(emitted_reward_bytes[i - 265])
+        }
+
+        let mut reward_per_second_bytes = [0_u8; 64];
+        self.reward_per_second
+            .to_little_endian(&mut reward_per_second_bytes[..32]);
+
+        for i in 297..329 {
+            res.push(reward_per_second_bytes[i - 297])
+        }
+
+        for i in self.cooldown.to_le_bytes() {
+            res.push(i)
+        }
+
+        res.push(self.emergency as u8);
+
+        for i in self.emission.pack() {
+            res.push(i)
+        }
+
+        res
+    }
+
+    pub fn unpack(src: Vec<u8>) -> Self {
+        let id = u64::from_le_bytes(src[0..8].try_into().unwrap());
+        let last_reward_timestamp = u64::from_le_bytes(src[8..16].try_into().unwrap());
+        let staking_token: ContractHash = src[16..48].try_into().unwrap();
+        let reward_token: ContractHash = src[48..80].try_into().unwrap();
+
+        let start_time = u64::from_le_bytes(src[80..88].try_into().unwrap());
+        let end_time = u64::from_le_bytes(src[88..96].try_into().unwrap());
+        let precision = u64::from_le_bytes(src[96..104].try_into().unwrap());
+
+        let owner: AccountHash = src[104..136].try_into().unwrap();
+        let total_reward = U256::from_little_endian(&src[136..168]);
+        let acc_token_per_share = U256::from_little_endian(&src[168..200]);
+        let total_staked = U256::from_little_endian(&src[200..232]);
+        let native_reward = src[232] != 0;
+        let unallocated_reward = U256::from_little_endian(&src[233..265]);
+        let emitted_reward = U256::from_little_endian(&src[265..297]);
+        let reward_per_second = U256::from_little_endian(&src[297..329]);
+        let cooldown = u64::from_le_bytes(src[329..337].try_into().unwrap());
+        let emergency = src[337] != 0;
+
+        let (_, emission_bytes) = src.split_at(338);
+        let emission = EmissionCurve::unpack(emission_bytes);
+
+        Self {
+            id,
+            last_reward_timestamp,
+            staking_token,
+            reward_token,
+            start_time,
+            end_time,
+            precision,
+            owner,
+            total_reward,
+            acc_token_per_share,
+            total_staked,
+            native_reward,
+            unallocated_reward,
+            emitted_reward,
+            reward_per_second,
+            cooldown,
+            emergency,
+            emission,
+        }
+    }
+
+    // gets the owner account from StakePool
+    // used in caller_is_recipient() function
+    pub fn unpack_owner(src: &[u8]) -> [u8; 32] {
+        let owner: [u8; 32] = src[104..136].try_into().unwrap();
+        owner
+    }
+}
+
+pub enum DepositScenario {
+    PendingZero,
+    EqualTokenPendingGreater,
+    EqualTokenPendingLesser,
+    ClaimRewardAndDeposit,
+}
+
+impl DepositScenario {
+    pub fn get_scenario(
+        pending: U256,
+        amount: U256,
+        reward_token: ContractHash,
+        staking_token: ContractHash,
+    ) -> Self {
+        if pending.is_zero() {
+            return Self::PendingZero;
+        }
+
+        let pending_gt_amount: bool = pending > amount;
+
+        let reward_token_eq_staking_token: bool = reward_token == staking_token;
+
+        if pending_gt_amount && reward_token_eq_staking_token {
+            return Self::EqualTokenPendingGreater;
+        }
+
+        if !pending_gt_amount && reward_token_eq_staking_token {
+            return Self::EqualTokenPendingLesser;
+        }
+
+        Self::ClaimRewardAndDeposit
+    }
+}
+
+pub enum WithdrawScenario {
+    PendingZero,
+    EqualTokens,
+    DifferentTokens,
+}
+
+impl WithdrawScenario {
+    pub fn get_scenario(
+        pending: U256,
+        reward_token: ContractHash,
+        staking_token: ContractHash,
+    ) -> Self {
+        if pending.is_zero() {
+            return Self::PendingZero;
+        }
+
+        let reward_token_eq_staking_token: bool = reward_token == staking_token;
+
+        if reward_token_eq_staking_token {
+            return Self::EqualTokens;
+        }
+
+        Self::DifferentTokens
+    }
+}
@@ -1,523 +1,1557 @@
-extern crate alloc;
-
-use crate::pool;
-
-use pool::{DepositScenario, StakePool, UserInfo, WithdrawScenario};
-
-use alloc::{
-    string::{String, ToString},
-    vec::Vec,
-};
-
-use casper_contract::{
-    contract_api::{
-        runtime::{self, revert},
-        storage,
-    },
-    unwrap_or_revert::UnwrapOrRevert,
-};
-
-use casper_types::{account::AccountHash, ApiError, ContractHash, ContractPackageHash, U256};
-
-use crate::{constants::KEY_NAME_INDEX, utils};
-use crate::{constants::KEY_NAME_INITIALIZED, interact_token::interact_erc20};
-
-use crate::constants::{
-    KEY_NAME_ADMIN, KEY_NAME_DIC_STAKE_INFOS, KEY_NAME_SELF_CONTRACT_HASH, KEY_NAME_USER_INFOS,
-};
-
-#[derive(Default)]
-pub struct StakeContract;
-
-impl StakeContract {
-    // init to create dictionary for lock info
-    pub fn init(&self, conthash: ContractPackageHash) {
-        let is_initialized: bool = utils::get_key(KEY_NAME_INITIALIZED);
-
-        if is_initialized {
-            revert(ApiError::PermissionDenied)
-        }
-
-        storage::new_dictionary(KEY_NAME_DIC_STAKE_INFOS).unwrap_or_revert();
-        storage::new_dictionary(KEY_NAME_USER_INFOS).unwrap_or_revert();
-
-        set_self_contract_hash(conthash);
-
-        utils::set_key(KEY_NAME_INDEX, 0u64);
-        utils::set_key(KEY_NAME_INITIALIZED, true);
-    }
-
-    // create a new staking pool.
-    pub fn add_pool(
-        &self,
-        staking_token: ContractHash,
-        reward_token: ContractHash,
-        start_time: u64,
-        end_time: u64,
-        precision: u64,
-        total_reward: U256,
-    ) {
-        let timestamp: u64 = runtime::get_blocktime().into();
-
-        if start_time < timestamp || end_time < timestamp {
-            revert(ApiError::InvalidArgument)
-        }
-
-        if total_reward.is_zero() {
-            revert(ApiError::InvalidArgument)
-        }
-
-        if !(18..=36).contains(&precision) {
-            revert(ApiError::InvalidArgument)
-        }
-
-        let seed_uref = *runtime::get_key(KEY_NAME_DIC_STAKE_INFOS)
-            .unwrap_or_revert()
-            .as_uref()
-            .unwrap_or_revert();
-
-        let current_index: u64 = utils::get_key(KEY_NAME_INDEX);
-
-        let new_pool = StakePool {
-            id: current_index,
-            last_reward_timestamp: 0u64,
-            staking_token,
-            reward_token,
-            start_time,
-            end_time,
-            precision,
-            owner: runtime::get_caller(),
-            acc_token_per_share: U256::zero(),
-            total_reward,
-            total_staked: U256::zero(),
-        };
-
-        let res = StakePool::pack(&new_pool);
-
-        let dictionary_item_key = make_pool_key_id(current_index);
-
-        storage::dictionary_put(seed_uref, &dictionary_item_key, res);
-
-        // transfer tokens to this contract
-        interact_erc20::default().transfer_from(
-            reward_token,
-            runtime::get_caller(),
-            self_contract_hash(),
-            total_reward,
-        );
-
-        // update the global counter for lock_id
-        utils::set_key(KEY_NAME_INDEX, current_index + 1u64);
-    }
-
-    // deposit staking tokens to earn reward tokens.
-    pub fn deposit(&self, amount: U256, pool_id: u64) {
-        if amount.is_zero() {
-            revert(ApiError::InvalidArgument)
-        }
-
-        let mut stake_pool = update_pool(pool_id);
-
-        let timestamp: u64 = runtime::get_blocktime().into();
-
-        if stake_pool.end_time < timestamp {
-            revert(ApiError::InvalidArgument)
-        }
-
-        let mut user_info = get_user_info_for_pool(pool_id);
-
-        let new_amount = amount + user_info.amount;
-
-        // first deposit
-        if user_info.amount.is_zero() {
-            user_info.amount = new_amount;
-
-            stake_pool.total_staked += amount;
-
-            user_info.reward_debt = new_amount
-                .checked_mul(stake_pool.acc_token_per_share)
-                .unwrap()
-                .checked_div(U256::from(10).pow(U256::from(stake_pool.precision)))
-                .unwrap();
-
-            // transfer tokens to this contract
-            interact_erc20::default().transfer_from(
-                stake_pool.staking_token,
-                runtime::get_caller(),
-                self_contract_hash(),
-                amount,
-            );
-            update_storage(&stake_pool, user_info, pool_id)
-        } else {
-            let acc_token_per_share = stake_pool.acc_token_per_share;
-
-            let precision = U256::from(10).pow(U256::from(stake_pool.precision));
-
-            let mut pending = user_info.amount * acc_token_per_share;
-            pending /= precision;
-            pending -= user_info.reward_debt;
-
-            // update amounts
-            user_info.amount = new_amount;
-            stake_pool.total_staked += amount;
-            user_info.reward_debt = (new_amount * acc_token_per_share) / precision;
-
-            let scenario = DepositScenario::get_scenario(
-                pending,
-                amount,
-                stake_pool.reward_token,
-                stake_pool.staking_token,
-            );
-
-            match scenario {
-                DepositScenario::PendingZero => interact_erc20::default().transfer_from(
-                    stake_pool.staking_token,
-                    runtime::get_caller(),
-                    self_contract_hash(),
-                    amount,
-                ),
-                DepositScenario::EqualTokenPendingGreater => interact_erc20::default().transfer(
-                    stake_pool.staking_token,
-                    runtime::get_caller(),
-                    pending - amount,
-                ),
-                DepositScenario::EqualTokenPendingLesser => interact_erc20::default()
-                    .transfer_from(
-                        stake_pool.staking_token,
-                        runtime::get_caller(),
-                        self_contract_hash(),
-                        amount - pending,
-                    ),
-                DepositScenario::ClaimRewardAndDeposit => {
-                    interact_erc20::default().transfer(
-                        stake_pool.reward_token,
-                        runtime::get_caller(),
-                        pending,
-                    );
-                    interact_erc20::default().transfer_from(
-                        stake_pool.staking_token,
-                        runtime::get_caller(),
-                        self_contract_hash(),
-                        amount,
-                    )
-                }
-            };
-
-            // update information at end in case
-            // token transfer reverts
-            update_storage(&stake_pool, user_info, pool_id)
-        }
-    }
-
-    // withdraw stake and claim reward token.
-    pub fn withdraw(&self, amount: U256, pool_id: u64) {
-        if amount.is_zero() {
-            revert(ApiError::InvalidArgument)
-        }
-        let mut user_info = get_user_info_for_pool(pool_id);
-        let mut stake_pool = update_pool(pool_id);
-
-        if user_info.amount.is_zero() {
-            revert(ApiError::InvalidArgument)
-        }
-
-        let new_amount = user_info.amount - amount;
-
-        let acc_token_per_share = stake_pool.acc_token_per_share;
-
-        let precision = U256::from(10).pow(U256::from(stake_pool.precision));
-
-        let mut pending = user_info.amount * acc_token_per_share;
-        pending /= precision;
-        pending -= user_info.reward_debt;
-
-        user_info.amount = new_amount;
-        stake_pool.total_staked -= amount;
-        user_info.reward_debt = (new_amount * acc_token_per_share) / precision;
-
-        let scenario = WithdrawScenario::get_scenario(
-            pending,
-            stake_pool.reward_token,
-            stake_pool.staking_token,
-        );
-
-        match scenario {
-            WithdrawScenario::PendingZero => {
-                interact_erc20::default().transfer(
-                    stake_pool.staking_token,
-                    runtime::get_caller(),
-                    amount,
-                );
-            }
-            WithdrawScenario::EqualTokens => interact_erc20::default().transfer(
-                stake_pool.staking_token,
-                runtime::get_caller(),
-                pending + amount,
-            ),
-            WithdrawScenario::DifferentTokens => {
-                interact_erc20::default().transfer(
-                    stake_pool.reward_token,
-                    runtime::get_caller(),
-                    pending,
-                );
-                interact_erc20::default().transfer(
-                    stake_pool.staking_token,
-                    runtime::get_caller(),
-                    amount,
-                )
-            }
-        };
-
-        // update information at end in case
-        // token transfer reverts
-        update_storage(&stake_pool, user_info, pool_id)
-    }
-
-    // Ends a stake pool early and returns
-    // tokens to pool owner
-    pub fn stop_reward(&self, pool_id: u64) {
-        let mut pool = update_pool(pool_id);
-
-        if pool.owner != runtime::get_caller() {
-            revert(ApiError::InvalidPurse)
-        }
-
-        let now = runtime::get_blocktime().into();
-
-        let old_end_time = pool.end_time;
-
-        if old_end_time <= now {
-            revert(ApiError::InvalidArgument)
-        }
-
-        pool.end_time = now;
-
-        let duration = U256::from(old_end_time - pool.start_time);
-
-        let amount = U256::from(old_end_time - get_max(now, pool.start_time)) * pool.total_reward;
-
-        interact_erc20::default().transfer(
-            pool.staking_token,
-            runtime::get_caller(),
-            amount / duration,
-        );
-
-        let updated_pool = StakePool::pack(&pool);
-
-        let seed_uref = *runtime::get_key(KEY_NAME_DIC_STAKE_INFOS)
-            .unwrap_or_revert_with(ApiError::User(1))
-            .as_uref()
-            .unwrap_or_revert_with(ApiError::User(2));
-
-        let dictionary_item_key = make_pool_key_id(pool_id);
-
-        storage::dictionary_put::<Vec<u8>>(seed_uref, &dictionary_item_key, updated_pool);
-    }
-
-    // emergency function for saving funds if
-    // something goes wrong.
-    // !! EMERGENCY USE ONLY !!
-    pub fn save_me(&self, token_hash: ContractHash, amount: U256) {
-        let admin = utils::get_key(KEY_NAME_ADMIN);
-
-        if runtime::get_caller() != admin {
-            revert(ApiError::InvalidPurse)
-        }
-
-        interact_erc20::default().transfer(token_hash, runtime::get_caller(), amount)
-    }
-    // withdraw without caring about rewards.
-    // !! EMERGENCY USE ONLY !!
-    pub fn emergency_withdraw(&self, pool_id: u64) {
-        let mut stake_pool = get_pool(pool_id);
-        let mut user_info = get_user_info_for_pool(pool_id);
-        let amount = user_info.amount;
-
-        if amount.is_zero() {
-            revert(ApiError::InvalidArgument)
-        }
-
-        user_info.amount = U256::zero();
-        stake_pool.total_staked -= amount;
-        user_info.reward_debt = U256::zero();
-
-        interact_erc20::default().transfer(stake_pool.staking_token, runtime::get_caller(), amount);
-
-        update_storage(&stake_pool, user_info, pool_id);
-    }
-
-    // sets new admin
-    // @account - account hash of the new admin
-    // ADMIN ONLY
-    pub fn set_admin(&self, account: AccountHash) {
-        let admin = utils::get_key(KEY_NAME_ADMIN);
-
-        if runtime::get_caller() != admin {
-            revert(ApiError::InvalidPurse)
-        }
-
-        utils::set_key(KEY_NAME_ADMIN, account)
-    }
-}
-
-// returns the pool with updated values
-fn update_pool(pool_id: u64) -> StakePool {
-    let now = runtime::get_blocktime().into();
-
-    let mut pool = get_pool(pool_id);
-
-    let last_reward_timestamp = pool.last_reward_timestamp;
-    if now <= last_reward_timestamp {
-        pool
-    } else {
-        let lp_supply = pool.total_staked;
-
-        if lp_supply.is_zero() || pool.start_time > now {
-            pool.last_reward_timestamp = now;
-            pool
-        } else {
-            let end = pool.end_time;
-
-            if last_reward_timestamp > end {
-                return pool;
-            }
-
-            let start = pool.start_time;
-            let precision = U256::from(10).pow(U256::from(pool.precision));
-
-            let reward_unlocked =
-                U256::from(get_min(now, end) - get_max(start, last_reward_timestamp));
-
-            let mut rewards = reward_unlocked * pool.total_reward;
-            rewards /= U256::from(end - start);
-            rewards *= precision;
-
-            pool.acc_token_per_share += rewards / pool.total_staked;
-            pool.last_reward_timestamp = now;
-            pool
-        }
-    }
-}
-
-// ensure id is less than current index
-fn is_id_valid(input: u64) {
-    let current_index: u64 = utils::get_key(KEY_NAME_INDEX);
-
-    if current_index < input {
-        revert(ApiError::InvalidArgument)
-    }
-}
-
-fn get_max(a: u64, b: u64) -> u64 {
-    if a > b {
-        a
-    } else {
-        b
-    }
-}
-
-fn get_min(a: u64, b: u64) -> u64 {
-    if a > b {
-        b
-    } else {
-        a
-    }
-}
-
-// retrieve lock and validate caller and lock info
-fn get_pool(pool_id: u64) -> StakePool {
-    is_id_valid(pool_id);
-
-    let seed_uref = *runtime::get_key(KEY_NAME_DIC_STAKE_INFOS)
-        .unwrap_or_revert_with(ApiError::User(5))
-        .as_uref()
-        .unwrap_or_revert_with(ApiError::User(6));
-
-    let dictionary_item_key = make_pool_key_id(pool_id);
-
-    let stake_pool_bytes = storage::dictionary_get::<Vec<u8>>(seed_uref, &dictionary_item_key)
-        .unwrap_or_revert_with(ApiError::MissingKey)
-        .unwrap_or_revert_with(ApiError::User(7));
-
-    StakePool::unpack(stake_pool_bytes)
-}
-
-// retrieve stake pool and validate caller and lock info
-fn update_storage(stake_pool: &StakePool, user_info: UserInfo, pool_id: u64) {
-    is_id_valid(pool_id);
-
-    let new_user_info = UserInfo::pack(&user_info);
-    let updated_pool = StakePool::pack(stake_pool);
-
-    let seed_uref = *runtime::get_key(KEY_NAME_DIC_STAKE_INFOS)
-        .unwrap_or_revert_with(ApiError::User(1))
-        .as_uref()
-        .unwrap_or_revert_with(ApiError::User(2));
-
-    let user_seed_uref = *runtime::get_key(KEY_NAME_USER_INFOS)
-        .unwrap_or_revert_with(ApiError::User(3))
-        .as_uref()
-        .unwrap_or_revert_with(ApiError::User(4));
-
-    let dictionary_item_key = make_pool_key_id(pool_id);
-    let user_info_key = make_user_key_by_id(pool_id);
-
-    storage::dictionary_put::<Vec<u8>>(seed_uref, &dictionary_item_key, updated_pool);
-    storage::dictionary_put::<Vec<u8>>(user_seed_uref, &user_info_key, new_user_info);
-}
-
-// retrieve user info
-fn get_user_info_for_pool(pool_id: u64) -> UserInfo {
-    is_id_valid(pool_id);
-
-    let seed_uref = *runtime::get_key(KEY_NAME_USER_INFOS)
-        .unwrap_or_revert()
-        .as_uref()
-        .unwrap_or_revert();
-
-    let dictionary_item_key = make_user_key_by_id(pool_id);
-
-    let user_info_bytes_option =
-        storage::dictionary_get::<Vec<u8>>(seed_uref, &dictionary_item_key);
-
-    match user_info_bytes_option.unwrap() {
-        Some(user) => UserInfo::unpack(user),
-        None => UserInfo::default(),
-    }
-}
-
-/*
-get contract hash
-*/
-fn self_contract_hash() -> ContractPackageHash {
-    utils::get_key(KEY_NAME_SELF_CONTRACT_HASH)
-}
-
-/*
-set contract hash - used in token transfer on claim() and lock( ).
-*/
-fn set_self_contract_hash(conthash: ContractPackageHash) {
-    utils::set_key(KEY_NAME_SELF_CONTRACT_HASH, conthash);
-}
-
-/*
-for organizing pools by index
-*/
-fn make_pool_key_id(pool_id: u64) -> String {
-    let c_hash = self_contract_hash().to_string();
-    let (pre_key, _) = c_hash.split_at(15);
-    let append = pool_id.to_string();
-
-    String::from(pre_key) + &append
-}
-
-/*
-for organizing pools by index
-*/
-fn make_user_key_by_id(pool_id: u64) -> String {
-    let account_hash = runtime::get_caller().to_string();
-    let (pre_key, _) = account_hash.split_at(15);
-    let append = pool_id.to_string();
-
-    String::from(pre_key) + &append
-}
+extern crate alloc;
+
+use crate::{checkpoint, emission::EmissionCurve, math, pool, Address, Error};
+
+use pool::{DepositScenario, StakePool, Unbond, UserInfo, WithdrawScenario};
+
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use casper_contract::{
+    contract_api::{
+        runtime::{self, revert},
+        storage, system,
+    },
+    unwrap_or_revert::UnwrapOrRevert,
+};
+
+use casper_types::{
+    account::AccountHash, ApiError, ContractHash, ContractPackageHash, Key, URef, U256,
+};
+
+use crate::{constants::KEY_NAME_INDEX, utils};
+use crate::{constants::KEY_NAME_INITIALIZED, interact_token::interact_erc20};
+
+use crate::constants::{
+    CSPR_DECIMALS, KEY_NAME_ADMIN, KEY_NAME_DIC_STAKE_INFOS, KEY_NAME_POOL_CHECKPOINTS,
+    KEY_NAME_POOL_CONTRACTS, KEY_NAME_POOL_CONTRACT_COUNT, KEY_NAME_POOL_METADATA,
+    KEY_NAME_REWARD_PURSE, KEY_NAME_SELF_CONTRACT_HASH, KEY_NAME_SINGLE_POOL, KEY_NAME_UNBONDS,
+    KEY_NAME_USER_CHECKPOINTS, KEY_NAME_USER_INFOS, MAX_COOLDOWN, MAX_POOL_METADATA_LENGTH,
+};
+
+#[derive(Default)]
+pub struct StakeContract;
+
+impl StakeContract {
+    // init to create dictionary for lock info
+    pub fn init(&self, conthash: ContractPackageHash) {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        let is_initialized: bool = utils::get_key(KEY_NAME_INITIALIZED);
+
+        if is_initialized {
+            revert(ApiError::PermissionDenied)
+        }
+
+        storage::new_dictionary(KEY_NAME_DIC_STAKE_INFOS).unwrap_or_revert();
+        storage::new_dictionary(KEY_NAME_USER_INFOS).unwrap_or_revert();
+        storage::new_dictionary(KEY_NAME_USER_CHECKPOINTS).unwrap_or_revert();
+        storage::new_dictionary(KEY_NAME_POOL_CHECKPOINTS).unwrap_or_revert();
+        storage::new_dictionary(KEY_NAME_POOL_METADATA).unwrap_or_revert();
+        storage::new_dictionary(KEY_NAME_UNBONDS).unwrap_or_revert();
+
+        // pool contracts installed by a factory don't keep a registry
+        if !runtime::has_key(KEY_NAME_SINGLE_POOL) {
+            storage::new_dictionary(KEY_NAME_POOL_CONTRACTS).unwrap_or_revert();
+            utils::set_key(KEY_NAME_POOL_CONTRACT_COUNT, 0u64);
+        }
+
+        set_self_contract_hash(conthash);
+
+        utils::set_key(KEY_NAME_INDEX, 0u64);
+        utils::set_key(KEY_NAME_INITIALIZED, true);
+    }
+
+    // create a new staking pool, returns its id.
+    // @emission - how total_reward is spread over start_time..end_time
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_pool(
+        &self,
+        staking_token: ContractHash,
+        reward_token: ContractHash,
+        start_time: u64,
+        end_time: u64,
+        precision: u64,
+        total_reward: U256,
+        emission: EmissionCurve,
+    ) -> u64 {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        let mut new_pool = new_pool(
+            staking_token,
+            reward_token,
+            start_time,
+            end_time,
+            precision,
+            total_reward,
+            false,
+            emission,
+        );
+
+        // transfer tokens from the immediate caller to this contract,
+        // so a contract like the pool factory can fund the pool.
+        // the pool only pays out the reward tokens that actually arrived
+        new_pool.total_reward = interact_erc20::default().transfer_from(
+            reward_token,
+            utils::get_immediate_caller(),
+            self_contract_hash(),
+            total_reward,
+        );
+
+        if new_pool.total_reward.is_zero() {
+            revert(ApiError::InvalidArgument)
+        }
+
+        // epochs have to add up to what actually arrived
+        new_pool
+            .emission
+            .validate(start_time, end_time, new_pool.total_reward);
+
+        save_new_pool(&new_pool);
+
+        new_pool.id
+    }
+
+    // create a new staking pool paying rewards in CSPR, returns its id.
+    // @purse - purse the total_reward motes are taken from
+    // @emission - how total_reward is spread over start_time..end_time
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_native_pool(
+        &self,
+        staking_token: ContractHash,
+        start_time: u64,
+        end_time: u64,
+        precision: u64,
+        total_reward: U256,
+        purse: URef,
+        emission: EmissionCurve,
+    ) -> u64 {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        let new_pool = new_pool(
+            staking_token,
+            ContractHash::new([0u8; 32]),
+            start_time,
+            end_time,
+            precision,
+            total_reward,
+            true,
+            emission,
+        );
+
+        // every native pool holds its rewards in its own purse
+        let reward_purse = system::create_purse();
+
+        system::transfer_from_purse_to_purse(
+            purse,
+            reward_purse,
+            utils::u256_to_u512(total_reward),
+            None,
+        )
+        .unwrap_or_revert();
+
+        runtime::put_key(&make_reward_purse_key(new_pool.id), reward_purse.into());
+
+        save_new_pool(&new_pool);
+
+        new_pool.id
+    }
+
+    // create a staking pool without a fixed end that emits
+    // reward_per_second for as long as its reserve lasts,
+    // returns its id.
+    // @total_reward - initial reserve, anyone can add to it with top_up_pool
+    pub fn add_rate_pool(
+        &self,
+        staking_token: ContractHash,
+        reward_token: ContractHash,
+        start_time: u64,
+        precision: u64,
+        reward_per_second: U256,
+        total_reward: U256,
+    ) -> u64 {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        if reward_per_second.is_zero() {
+            revert(ApiError::InvalidArgument)
+        }
+
+        let mut new_pool = new_pool(
+            staking_token,
+            reward_token,
+            start_time,
+            u64::MAX,
+            precision,
+            total_reward,
+            false,
+            EmissionCurve::Linear,
+        );
+
+        new_pool.reward_per_second = reward_per_second;
+
+        // transfer tokens from the immediate caller to this contract,
+        // so a contract like the pool factory can fund the pool.
+        // the pool only pays out the reward tokens that actually arrived
+        new_pool.total_reward = interact_erc20::default().transfer_from(
+            reward_token,
+            utils::get_immediate_caller(),
+            self_contract_hash(),
+            total_reward,
+        );
+
+        if new_pool.total_reward.is_zero() {
+            revert(ApiError::InvalidArgument)
+        }
+
+        save_new_pool(&new_pool);
+
+        new_pool.id
+    }
+
+    // adds reward tokens to the reserve of a rate pool,
+    // returns the amount added. Callable by anyone
+    pub fn top_up_pool(&self, pool_id: u64, amount: U256) -> U256 {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        if amount.is_zero() {
+            revert(ApiError::InvalidArgument)
+        }
+
+        // emission so far is capped by the old reserve
+        let mut pool = update_pool(pool_id);
+
+        let now: u64 = runtime::get_blocktime().into();
+
+        if pool.reward_per_second.is_zero() || pool.end_time <= now {
+            revert(ApiError::InvalidArgument)
+        }
+
+        // contracts top up from their own balance, like deposit
+        let received = interact_erc20::default().transfer_from(
+            pool.reward_token,
+            staker(),
+            self_contract_hash(),
+            amount,
+        );
+
+        pool.total_reward = math::add(pool.total_reward, received).unwrap_or_revert();
+
+        save_pool(&pool);
+
+        received
+    }
+
+    // deposit staking tokens to earn reward tokens,
+    // returns the amount added to the caller's stake.
+    pub fn deposit(&self, amount: U256, pool_id: u64) -> U256 {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        if amount.is_zero() {
+            revert(ApiError::InvalidArgument)
+        }
+
+        let mut stake_pool = update_pool(pool_id);
+
+        if stake_pool.emergency {
+            revert(Error::EmergencyMode)
+        }
+
+        let timestamp: u64 = runtime::get_blocktime().into();
+
+        if stake_pool.end_time < timestamp {
+            revert(ApiError::InvalidArgument)
+        }
+
+        let mut user_info = get_user_info_for_pool(pool_id);
+
+        // first deposit
+        if user_info.amount.is_zero() {
+            // transfer tokens to this contract and stake
+            // what actually arrived
+            let received = interact_erc20::default().transfer_from(
+                stake_pool.staking_token,
+                staker(),
+                self_contract_hash(),
+                amount,
+            );
+
+            let new_amount = math::add(received, user_info.amount).unwrap_or_revert();
+
+            user_info.amount = new_amount;
+
+            stake_pool.total_staked =
+                math::add(stake_pool.total_staked, received).unwrap_or_revert();
+
+            user_info.reward_debt = math::accrued(
+                new_amount,
+                stake_pool.acc_token_per_share,
+                stake_pool.precision,
+            )
+            .unwrap_or_revert();
+
+            write_checkpoints(&stake_pool, &user_info);
+
+            update_storage(&stake_pool, user_info, pool_id);
+
+            received
+        } else {
+            let acc_token_per_share = stake_pool.acc_token_per_share;
+
+            let pending = math::pending(
+                user_info.amount,
+                acc_token_per_share,
+                stake_pool.precision,
+                user_info.reward_debt,
+            )
+            .unwrap_or_revert();
+
+            let scenario = DepositScenario::get_scenario(
+                pending,
+                amount,
+                stake_pool.reward_token,
+                stake_pool.staking_token,
+            );
+
+            // the stake grows by what actually arrived
+            // (plus any pending reward kept as stake)
+            let received = match scenario {
+                DepositScenario::PendingZero => interact_erc20::default().transfer_from(
+                    stake_pool.staking_token,
+                    staker(),
+                    self_contract_hash(),
+                    amount,
+                ),
+                DepositScenario::EqualTokenPendingGreater => {
+                    interact_erc20::default().transfer(
+                        stake_pool.staking_token,
+                        staker(),
+                        math::sub(pending, amount).unwrap_or_revert(),
+                    );
+                    amount
+                }
+                DepositScenario::EqualTokenPendingLesser => {
+                    let transferred = interact_erc20::default().transfer_from(
+                        stake_pool.staking_token,
+                        staker(),
+                        self_contract_hash(),
+                        math::sub(amount, pending).unwrap_or_revert(),
+                    );
+                    math::add(pending, transferred).unwrap_or_revert()
+                }
+                DepositScenario::ClaimRewardAndDeposit => {
+                    transfer_reward(&stake_pool, staker(), pending);
+                    interact_erc20::default().transfer_from(
+                        stake_pool.staking_token,
+                        staker(),
+                        self_contract_hash(),
+                        amount,
+                    )
+                }
+            };
+
+            // update amounts
+            let new_amount = math::add(received, user_info.amount).unwrap_or_revert();
+
+            user_info.amount = new_amount;
+            stake_pool.total_staked =
+                math::add(stake_pool.total_staked, received).unwrap_or_revert();
+            user_info.reward_debt =
+                math::accrued(new_amount, acc_token_per_share, stake_pool.precision)
+                    .unwrap_or_revert();
+
+            write_checkpoints(&stake_pool, &user_info);
+
+            // update information at end in case
+            // token transfer reverts
+            update_storage(&stake_pool, user_info, pool_id);
+
+            received
+        }
+    }
+
+    // withdraw stake and claim reward token,
+    // returns the amount withdrawn and the rewards paid.
+    pub fn withdraw(&self, amount: U256, pool_id: u64) -> (U256, U256) {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        if amount.is_zero() {
+            revert(ApiError::InvalidArgument)
+        }
+        let mut user_info = get_user_info_for_pool(pool_id);
+        let mut stake_pool = update_pool(pool_id);
+
+        if stake_pool.emergency {
+            revert(Error::EmergencyMode)
+        }
+
+        // pools with a cooldown only release stake through request_withdraw
+        if user_info.amount.is_zero() || stake_pool.cooldown > 0 {
+            revert(ApiError::InvalidArgument)
+        }
+
+        if amount > user_info.amount {
+            revert(Error::InsufficientStake)
+        }
+
+        let new_amount = math::sub(user_info.amount, amount).unwrap_or_revert();
+
+        let acc_token_per_share = stake_pool.acc_token_per_share;
+
+        let pending = math::pending(
+            user_info.amount,
+            acc_token_per_share,
+            stake_pool.precision,
+            user_info.reward_debt,
+        )
+        .unwrap_or_revert();
+
+        user_info.amount = new_amount;
+        stake_pool.total_staked = math::sub(stake_pool.total_staked, amount).unwrap_or_revert();
+        user_info.reward_debt =
+            math::accrued(new_amount, acc_token_per_share, stake_pool.precision).unwrap_or_revert();
+
+        let scenario = WithdrawScenario::get_scenario(
+            pending,
+            stake_pool.reward_token,
+            stake_pool.staking_token,
+        );
+
+        match scenario {
+            WithdrawScenario::PendingZero => {
+                interact_erc20::default().transfer(stake_pool.staking_token, staker(), amount);
+            }
+            WithdrawScenario::EqualTokens => interact_erc20::default().transfer(
+                stake_pool.staking_token,
+                staker(),
+                math::add(pending, amount).unwrap_or_revert(),
+            ),
+            WithdrawScenario::DifferentTokens => {
+                transfer_reward(&stake_pool, staker(), pending);
+                interact_erc20::default().transfer(stake_pool.staking_token, staker(), amount)
+            }
+        };
+
+        write_checkpoints(&stake_pool, &user_info);
+
+        // update information at end in case
+        // token transfer reverts
+        update_storage(&stake_pool, user_info, pool_id);
+
+        (amount, pending)
+    }
+
+    // stops amount of the caller's stake from earning rewards
+    // and starts its cooldown, pending rewards are paid now.
+    // Unbonding more resets the cooldown of the whole unbond.
+    // returns the time the unbond can be completed at
+    pub fn request_withdraw(&self, amount: U256, pool_id: u64) -> u64 {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        let mut user_info = get_user_info_for_pool(pool_id);
+        let mut stake_pool = update_pool(pool_id);
+
+        if stake_pool.emergency {
+            revert(Error::EmergencyMode)
+        }
+
+        if amount.is_zero() || stake_pool.cooldown == 0 {
+            revert(ApiError::InvalidArgument)
+        }
+
+        if amount > user_info.amount {
+            revert(Error::InsufficientStake)
+        }
+
+        let pending = math::pending(
+            user_info.amount,
+            stake_pool.acc_token_per_share,
+            stake_pool.precision,
+            user_info.reward_debt,
+        )
+        .unwrap_or_revert();
+
+        user_info.amount = math::sub(user_info.amount, amount).unwrap_or_revert();
+        stake_pool.total_staked = math::sub(stake_pool.total_staked, amount).unwrap_or_revert();
+        user_info.reward_debt = math::accrued(
+            user_info.amount,
+            stake_pool.acc_token_per_share,
+            stake_pool.precision,
+        )
+        .unwrap_or_revert();
+
+        let now: u64 = runtime::get_blocktime().into();
+
+        let mut unbond = get_unbond(staker(), pool_id);
+        unbond.amount = math::add(unbond.amount, amount).unwrap_or_revert();
+        unbond.release_time = now.saturating_add(stake_pool.cooldown);
+
+        if !pending.is_zero() {
+            transfer_reward(&stake_pool, staker(), pending);
+        }
+
+        write_checkpoints(&stake_pool, &user_info);
+
+        save_unbond(pool_id, &unbond);
+        update_storage(&stake_pool, user_info, pool_id);
+
+        unbond.release_time
+    }
+
+    // releases the caller's unbonded stake once its cooldown
+    // is over, or right away in emergency mode.
+    // returns the amount released
+    pub fn complete_withdraw(&self, pool_id: u64) -> U256 {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        let stake_pool = get_pool(pool_id);
+        let unbond = get_unbond(staker(), pool_id);
+
+        let now: u64 = runtime::get_blocktime().into();
+
+        if unbond.amount.is_zero() || (now < unbond.release_time && !stake_pool.emergency) {
+            revert(ApiError::InvalidArgument)
+        }
+
+        save_unbond(pool_id, &Unbond::default());
+
+        interact_erc20::default().transfer(stake_pool.staking_token, staker(), unbond.amount);
+
+        unbond.amount
+    }
+
+    // sets how long request_withdraw holds stake before
+    // complete_withdraw releases it, 0 disables the cooldown.
+    // Only while nothing is staked, so stakers keep the
+    // exit terms they entered under
+    // POOL OWNER ONLY
+    pub fn set_cooldown(&self, pool_id: u64, cooldown: u64) {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        let mut pool = get_pool(pool_id);
+
+        if pool.owner != runtime::get_caller() {
+            revert(ApiError::InvalidPurse)
+        }
+
+        if cooldown > MAX_COOLDOWN {
+            revert(ApiError::InvalidArgument)
+        }
+
+        if !pool.total_staked.is_zero() {
+            revert(ApiError::PermissionDenied)
+        }
+
+        pool.cooldown = cooldown;
+
+        save_pool(&pool);
+    }
+
+    // turns emergency mode of a pool on or off. In emergency mode
+    // deposits, withdrawals and harvests are disabled, stakers get
+    // their principal back through emergency_withdraw and the
+    // rewards left stop emitting until the mode is turned off
+    // ADMIN OR POOL OWNER ONLY
+    pub fn set_emergency(&self, pool_id: u64, emergency: bool) {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        let mut pool = get_pool(pool_id);
+
+        let caller = runtime::get_caller();
+        let admin: AccountHash = utils::get_key(KEY_NAME_ADMIN);
+
+        if pool.owner != caller && admin != caller {
+            revert(ApiError::InvalidPurse)
+        }
+
+        if pool.emergency == emergency {
+            revert(ApiError::InvalidArgument)
+        }
+
+        if emergency {
+            // rewards emitted so far stay earned
+            pool = accrue_pool(pool);
+        } else {
+            // nobody earns the rewards emitted while the pool was
+            // in emergency mode, keep them for the pool owner
+            let skipped = emit_rewards(&mut pool);
+            pool.unallocated_reward =
+                math::add(pool.unallocated_reward, skipped).unwrap_or_revert();
+        }
+
+        pool.emergency = emergency;
+
+        save_pool(&pool);
+    }
+
+    // Ends a stake pool early and returns the
+    // reward budget stakers can no longer earn
+    // to pool owner, returns the refunded amount.
+    // Pools in emergency mode can be stopped after
+    // their end to recover the frozen rewards
+    pub fn stop_reward(&self, pool_id: u64) -> U256 {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        let mut pool = update_pool(pool_id);
+
+        if pool.owner != runtime::get_caller() {
+            revert(ApiError::InvalidPurse)
+        }
+
+        let now = runtime::get_blocktime().into();
+
+        if pool.end_time <= now && !pool.emergency {
+            revert(ApiError::InvalidArgument)
+        }
+
+        // rewards not emitted yet, plus emitted
+        // rewards that nobody earned
+        let amount = math::add(
+            math::sub(pool.total_reward, pool.emitted_reward).unwrap_or_revert(),
+            pool.unallocated_reward,
+        )
+        .unwrap_or_revert();
+
+        pool.end_time = get_min(pool.end_time, now);
+        pool.total_reward = pool.emitted_reward;
+        pool.unallocated_reward = U256::zero();
+        // update_pool doesn't emit in emergency mode, the time since
+        // has been refunded and mustn't be emitted again once it ends
+        pool.last_reward_timestamp = now;
+
+        save_pool(&pool);
+
+        transfer_reward(&pool, Address::from(runtime::get_caller()), amount);
+
+        amount
+    }
+
+    // sends the pool owner rewards that were emitted
+    // while nothing was staked in the pool,
+    // returns the amount sent
+    // POOL OWNER ONLY
+    pub fn reclaim_unallocated(&self, pool_id: u64) -> U256 {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        let mut pool = update_pool(pool_id);
+
+        if pool.owner != runtime::get_caller() {
+            revert(ApiError::InvalidPurse)
+        }
+
+        let amount = pool.unallocated_reward;
+
+        if amount.is_zero() {
+            revert(ApiError::InvalidArgument)
+        }
+
+        pool.unallocated_reward = U256::zero();
+
+        save_pool(&pool);
+
+        transfer_reward(&pool, Address::from(runtime::get_caller()), amount);
+
+        amount
+    }
+
+    // replaces the linear emission of a pool
+    // before it starts emitting rewards
+    // @curve - the new emission schedule
+    // POOL OWNER ONLY
+    pub fn set_emission_curve(&self, pool_id: u64, curve: EmissionCurve) {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        let mut pool = get_pool(pool_id);
+
+        if pool.owner != runtime::get_caller() {
+            revert(ApiError::InvalidPurse)
+        }
+
+        let now: u64 = runtime::get_blocktime().into();
+
+        // rate pools have no window to shape
+        if pool.start_time <= now || !pool.reward_per_second.is_zero() {
+            revert(ApiError::InvalidArgument)
+        }
+
+        curve.validate(pool.start_time, pool.end_time, pool.total_reward);
+
+        pool.emission = curve;
+
+        save_pool(&pool);
+    }
+
+    // emergency function for saving funds if
+    // something goes wrong.
+    // !! EMERGENCY USE ONLY !!
+    pub fn save_me(&self, token_hash: ContractHash, amount: U256) -> U256 {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        let admin = utils::get_key(KEY_NAME_ADMIN);
+
+        if runtime::get_caller() != admin {
+            revert(ApiError::InvalidPurse)
+        }
+
+        interact_erc20::default().transfer(
+            token_hash,
+            Address::from(runtime::get_caller()),
+            amount,
+        );
+
+        amount
+    }
+    // withdraw without caring about rewards from a pool
+    // in emergency mode, returns the amount withdrawn.
+    // !! EMERGENCY USE ONLY !!
+    pub fn emergency_withdraw(&self, pool_id: u64) -> U256 {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        let mut stake_pool = get_pool(pool_id);
+        let mut user_info = get_user_info_for_pool(pool_id);
+        let amount = user_info.amount;
+
+        if !stake_pool.emergency {
+            revert(Error::NotEmergencyMode)
+        }
+
+        if amount.is_zero() {
+            revert(ApiError::InvalidArgument)
+        }
+
+        // the pending rewards given up go back to the pool owner. The
+        // way out must never fail on them, so a bad position forfeits 0
+        let forfeited = math::pending(
+            amount,
+            stake_pool.acc_token_per_share,
+            stake_pool.precision,
+            user_info.reward_debt,
+        )
+        .unwrap_or_default();
+
+        stake_pool.unallocated_reward =
+            math::add(stake_pool.unallocated_reward, forfeited).unwrap_or_revert();
+
+        user_info.amount = U256::zero();
+        stake_pool.total_staked = math::sub(stake_pool.total_staked, amount).unwrap_or_revert();
+        user_info.reward_debt = U256::zero();
+
+        interact_erc20::default().transfer(stake_pool.staking_token, staker(), amount);
+
+        write_checkpoints(&stake_pool, &user_info);
+
+        update_storage(&stake_pool, user_info, pool_id);
+
+        amount
+    }
+
+    // claims the caller's pending rewards from several pools,
+    // rewards in the same token are paid in one transfer.
+    // returns the rewards paid by each pool
+    pub fn harvest_many(&self, pool_ids: Vec<u64>) -> Vec<U256> {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        let batch = Batch::new();
+        let mut payouts = Payouts::default();
+        let mut rewards = Vec::new();
+
+        for pool_id in pool_ids {
+            let pool = accrue_pool(batch.get_pool(pool_id));
+            let mut user_info = batch.get_user_info(pool_id);
+
+            if pool.emergency {
+                revert(Error::EmergencyMode)
+            }
+
+            if user_info.amount.is_zero() {
+                revert(ApiError::InvalidArgument)
+            }
+
+            let pending = math::pending(
+                user_info.amount,
+                pool.acc_token_per_share,
+                pool.precision,
+                user_info.reward_debt,
+            )
+            .unwrap_or_revert();
+
+            user_info.reward_debt =
+                math::accrued(user_info.amount, pool.acc_token_per_share, pool.precision)
+                    .unwrap_or_revert();
+
+            payouts.add_reward(&pool, pending);
+            rewards.push(pending);
+
+            batch.save(&pool, &user_info);
+        }
+
+        payouts.pay(staker());
+
+        rewards
+    }
+
+    // withdraws stake and claims rewards from several pools
+    // @amounts - amount to withdraw from the pool at the same position
+    // returns the rewards paid by each pool
+    pub fn withdraw_many(&self, pool_ids: Vec<u64>, amounts: Vec<U256>) -> Vec<U256> {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        if pool_ids.len() != amounts.len() {
+            revert(ApiError::InvalidArgument)
+        }
+
+        let batch = Batch::new();
+        let mut payouts = Payouts::default();
+        let mut rewards = Vec::new();
+
+        for (pool_id, amount) in pool_ids.into_iter().zip(amounts) {
+            let mut pool = accrue_pool(batch.get_pool(pool_id));
+            let mut user_info = batch.get_user_info(pool_id);
+
+            if pool.emergency {
+                revert(Error::EmergencyMode)
+            }
+
+            if amount.is_zero() || pool.cooldown > 0 {
+                revert(ApiError::InvalidArgument)
+            }
+
+            if amount > user_info.amount {
+                revert(Error::InsufficientStake)
+            }
+
+            let pending = math::pending(
+                user_info.amount,
+                pool.acc_token_per_share,
+                pool.precision,
+                user_info.reward_debt,
+            )
+            .unwrap_or_revert();
+
+            user_info.amount = math::sub(user_info.amount, amount).unwrap_or_revert();
+            pool.total_staked = math::sub(pool.total_staked, amount).unwrap_or_revert();
+            user_info.reward_debt =
+                math::accrued(user_info.amount, pool.acc_token_per_share, pool.precision)
+                    .unwrap_or_revert();
+
+            payouts.add_token(pool.staking_token, amount);
+            payouts.add_reward(&pool, pending);
+            rewards.push(pending);
+
+            write_checkpoints(&pool, &user_info);
+
+            batch.save(&pool, &user_info);
+        }
+
+        payouts.pay(staker());
+
+        rewards
+    }
+
+    // brings the reward accounting of several pools
+    // up to date, callable by anyone
+    pub fn mass_update_pools(&self, pool_ids: Vec<u64>) {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        let batch = Batch::new();
+
+        for pool_id in pool_ids {
+            batch.save_pool(&accrue_pool(batch.get_pool(pool_id)));
+        }
+    }
+
+    // staked amount of an account in a pool as of timestamp
+    pub fn stake_at(&self, account: Address, pool_id: u64, timestamp: u64) -> U256 {
+        is_id_valid(pool_id);
+
+        read_checkpoint(
+            KEY_NAME_USER_CHECKPOINTS,
+            &make_account_key(account, pool_id),
+            timestamp,
+        )
+    }
+
+    // total staked amount of a pool as of timestamp
+    pub fn total_staked_at(&self, pool_id: u64, timestamp: u64) -> U256 {
+        is_id_valid(pool_id);
+
+        read_checkpoint(
+            KEY_NAME_POOL_CHECKPOINTS,
+            &make_pool_key_id(pool_id),
+            timestamp,
+        )
+    }
+
+    // pool info with rewards accrued up to now
+    pub fn get_pool(&self, pool_id: u64) -> (BTreeMap<String, Key>, BTreeMap<String, U256>) {
+        update_pool(pool_id).to_named_values()
+    }
+
+    // stake of an account in a pool, with the rewards it
+    // could claim now as pending_reward and its unbond
+    pub fn get_user_info(&self, pool_id: u64, account: Address) -> BTreeMap<String, U256> {
+        let pool = update_pool(pool_id);
+        let user_info = get_user_info_for_account(account, pool_id);
+
+        let pending = math::pending(
+            user_info.amount,
+            pool.acc_token_per_share,
+            pool.precision,
+            user_info.reward_debt,
+        )
+        .unwrap_or_revert();
+
+        let unbond = get_unbond(account, pool_id);
+
+        let mut values = user_info.to_named_values();
+        values.insert(String::from("pending_reward"), pending);
+        values.insert(String::from("unbonding"), unbond.amount);
+        values.insert(
+            String::from("unbond_release_time"),
+            U256::from(unbond.release_time),
+        );
+
+        values
+    }
+
+    // sets the name, description and url shown for a pool
+    // POOL OWNER ONLY
+    pub fn set_pool_metadata(&self, pool_id: u64, name: String, description: String, url: String) {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        let pool = get_pool(pool_id);
+
+        if pool.owner != runtime::get_caller() {
+            revert(ApiError::InvalidPurse)
+        }
+
+        if name.len() > MAX_POOL_METADATA_LENGTH
+            || description.len() > MAX_POOL_METADATA_LENGTH
+            || url.len() > MAX_POOL_METADATA_LENGTH
+        {
+            revert(ApiError::InvalidArgument)
+        }
+
+        let mut metadata = BTreeMap::new();
+        metadata.insert(String::from("name"), name);
+        metadata.insert(String::from("description"), description);
+        metadata.insert(String::from("url"), url);
+
+        let seed_uref = *runtime::get_key(KEY_NAME_POOL_METADATA)
+            .unwrap_or_revert()
+            .as_uref()
+            .unwrap_or_revert();
+
+        storage::dictionary_put(seed_uref, &pool_id.to_string(), metadata);
+    }
+
+    // name, description and url of a pool,
+    // empty for pools that never set them
+    pub fn get_pool_metadata(&self, pool_id: u64) -> BTreeMap<String, String> {
+        is_id_valid(pool_id);
+
+        let seed_uref = *runtime::get_key(KEY_NAME_POOL_METADATA)
+            .unwrap_or_revert()
+            .as_uref()
+            .unwrap_or_revert();
+
+        match storage::dictionary_get::<BTreeMap<String, String>>(seed_uref, &pool_id.to_string())
+            .unwrap_or_revert()
+        {
+            Some(metadata) => metadata,
+            None => {
+                let mut metadata = BTreeMap::new();
+                metadata.insert(String::from("name"), String::new());
+                metadata.insert(String::from("description"), String::new());
+                metadata.insert(String::from("url"), String::new());
+                metadata
+            }
+        }
+    }
+
+    // sets new admin
+    // @account - account hash of the new admin
+    // ADMIN ONLY
+    pub fn set_admin(&self, account: AccountHash) {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        let admin = utils::get_key(KEY_NAME_ADMIN);
+
+        if runtime::get_caller() != admin {
+            revert(ApiError::InvalidPurse)
+        }
+
+        utils::set_key(KEY_NAME_ADMIN, account)
+    }
+}
+
+// validates pool parameters and builds a pool at the next index
+#[allow(clippy::too_many_arguments)]
+fn new_pool(
+    staking_token: ContractHash,
+    reward_token: ContractHash,
+    start_time: u64,
+    end_time: u64,
+    precision: u64,
+    total_reward: U256,
+    native_reward: bool,
+    emission: EmissionCurve,
+) -> StakePool {
+    let timestamp: u64 = runtime::get_blocktime().into();
+
+    if start_time < timestamp || end_time <= start_time {
+        revert(ApiError::InvalidArgument)
+    }
+
+    if total_reward.is_zero() {
+        revert(ApiError::InvalidArgument)
+    }
+
+    emission.validate(start_time, end_time, total_reward);
+
+    // 0 picks the precision from the tokens' decimals,
+    // anything else is an explicit override
+    let precision = if precision == 0 {
+        auto_precision(staking_token, reward_token, native_reward)
+    } else {
+        precision
+    };
+
+    if !(18..=36).contains(&precision) {
+        revert(ApiError::InvalidArgument)
+    }
+
+    let current_index: u64 = utils::get_key(KEY_NAME_INDEX);
+
+    // pool contracts installed by a factory hold a single pool
+    if current_index > 0 && runtime::has_key(KEY_NAME_SINGLE_POOL) {
+        revert(ApiError::PermissionDenied)
+    }
+
+    StakePool {
+        id: current_index,
+        last_reward_timestamp: 0u64,
+        staking_token,
+        reward_token,
+        start_time,
+        end_time,
+        precision,
+        owner: runtime::get_caller(),
+        acc_token_per_share: U256::zero(),
+        total_reward,
+        total_staked: U256::zero(),
+        native_reward,
+        unallocated_reward: U256::zero(),
+        emitted_reward: U256::zero(),
+        reward_per_second: U256::zero(),
+        cooldown: 0u64,
+        emergency: false,
+        emission,
+    }
+}
+
+// picks acc_token_per_share scaling for a pool. Starts from the
+// staking token's decimals plus 18 so a single staked unit still
+// accrues rewards, then gives up digits to reward tokens with more
+// than 18 decimals so reward * precision stays inside U256
+fn auto_precision(
+    staking_token: ContractHash,
+    reward_token: ContractHash,
+    native_reward: bool,
+) -> u64 {
+    let staking_decimals = u64::from(interact_erc20::default().decimals(staking_token));
+
+    let reward_decimals = if native_reward {
+        u64::from(CSPR_DECIMALS)
+    } else {
+        u64::from(interact_erc20::default().decimals(reward_token))
+    };
+
+    (staking_decimals + 18)
+        .saturating_sub(reward_decimals.saturating_sub(18))
+        .clamp(18, 36)
+}
+
+// stores a freshly built pool and bumps the pool index
+fn save_new_pool(new_pool: &StakePool) {
+    let seed_uref = *runtime::get_key(KEY_NAME_DIC_STAKE_INFOS)
+        .unwrap_or_revert()
+        .as_uref()
+        .unwrap_or_revert();
+
+    let res = StakePool::pack(new_pool);
+
+    let dictionary_item_key = make_pool_key_id(new_pool.id);
+
+    storage::dictionary_put(seed_uref, &dictionary_item_key, res);
+
+    // update the global counter for pool_id
+    utils::set_key(
+        KEY_NAME_INDEX,
+        new_pool
+            .id
+            .checked_add(1)
+            .unwrap_or_revert_with(Error::Overflow),
+    );
+}
+
+// pays out rewards in the pool's reward token, or
+// from its reward purse for CSPR pools
+fn transfer_reward(pool: &StakePool, recipient: Address, amount: U256) {
+    if pool.native_reward {
+        transfer_native_reward(pool.id, recipient, amount)
+    } else {
+        interact_erc20::default().transfer(pool.reward_token, recipient, amount)
+    }
+}
+
+// pays out CSPR from the reward purse of a pool,
+// only accounts can receive it
+fn transfer_native_reward(pool_id: u64, recipient: Address, amount: U256) {
+    let account = *recipient
+        .as_account_hash()
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    system::transfer_from_purse_to_account(
+        get_reward_purse(pool_id),
+        account,
+        utils::u256_to_u512(amount),
+        None,
+    )
+    .unwrap_or_revert();
+}
+
+// returns the pool with updated values
+fn update_pool(pool_id: u64) -> StakePool {
+    accrue_pool(get_pool(pool_id))
+}
+
+// accrues the rewards emitted since the pool was last updated
+fn accrue_pool(mut pool: StakePool) -> StakePool {
+    // rewards are frozen in emergency mode
+    if pool.emergency {
+        return pool;
+    }
+
+    let rewards = emit_rewards(&mut pool);
+
+    if pool.total_staked.is_zero() {
+        // nobody can earn rewards emitted while nothing
+        // is staked, keep them for the pool owner
+        pool.unallocated_reward = math::add(pool.unallocated_reward, rewards).unwrap_or_revert();
+    } else {
+        let increment =
+            math::acc_increment(rewards, pool.precision, pool.total_staked).unwrap_or_revert();
+
+        pool.acc_token_per_share =
+            math::add(pool.acc_token_per_share, increment).unwrap_or_revert();
+    }
+
+    pool
+}
+
+// moves the emission of a pool forward to now,
+// returns the rewards emitted since last_reward_timestamp
+fn emit_rewards(pool: &mut StakePool) -> U256 {
+    let now = runtime::get_blocktime().into();
+
+    let last_reward_timestamp = pool.last_reward_timestamp;
+
+    if now <= last_reward_timestamp {
+        U256::zero()
+    } else if pool.start_time > now {
+        pool.last_reward_timestamp = now;
+        U256::zero()
+    } else {
+        let end = pool.end_time;
+
+        if last_reward_timestamp >= end {
+            return U256::zero();
+        }
+
+        let start = pool.start_time;
+
+        let from = get_max(start, last_reward_timestamp);
+        let to = get_min(now, end);
+
+        let rewards = if pool.reward_per_second.is_zero() {
+            pool.emission
+                .rewards_between(pool.total_reward, start, end, from, to)
+                .unwrap_or_revert()
+        } else {
+            // rate pools emit until their reserve runs out
+            let elapsed = to.checked_sub(from).unwrap_or_revert_with(Error::Underflow);
+            let rewards = math::mul(pool.reward_per_second, U256::from(elapsed)).unwrap_or_revert();
+            let reserve = math::sub(pool.total_reward, pool.emitted_reward).unwrap_or_revert();
+
+            if rewards > reserve {
+                reserve
+            } else {
+                rewards
+            }
+        };
+
+        pool.emitted_reward = math::add(pool.emitted_reward, rewards).unwrap_or_revert();
+        pool.last_reward_timestamp = now;
+
+        rewards
+    }
+}
+
+// ensure id is less than current index
+fn is_id_valid(input: u64) {
+    let current_index: u64 = utils::get_key(KEY_NAME_INDEX);
+
+    if current_index < input {
+        revert(ApiError::InvalidArgument)
+    }
+}
+
+fn get_max(a: u64, b: u64) -> u64 {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+fn get_min(a: u64, b: u64) -> u64 {
+    if a > b {
+        b
+    } else {
+        a
+    }
+}
+
+// retrieve lock and validate caller and lock info
+fn get_pool(pool_id: u64) -> StakePool {
+    is_id_valid(pool_id);
+
+    let seed_uref = *runtime::get_key(KEY_NAME_DIC_STAKE_INFOS)
+        .unwrap_or_revert_with(ApiError::User(5))
+        .as_uref()
+        .unwrap_or_revert_with(ApiError::User(6));
+
+    let dictionary_item_key = make_pool_key_id(pool_id);
+
+    let stake_pool_bytes = storage::dictionary_get::<Vec<u8>>(seed_uref, &dictionary_item_key)
+        .unwrap_or_revert_with(ApiError::MissingKey)
+        .unwrap_or_revert_with(ApiError::User(7));
+
+    StakePool::unpack(stake_pool_bytes)
+}
+
+// store pool info
+fn save_pool(stake_pool: &StakePool) {
+    let updated_pool = StakePool::pack(stake_pool);
+
+    let seed_uref = *runtime::get_key(KEY_NAME_DIC_STAKE_INFOS)
+        .unwrap_or_revert_with(ApiError::User(1))
+        .as_uref()
+        .unwrap_or_revert_with(ApiError::User(2));
+
+    let dictionary_item_key = make_pool_key_id(stake_pool.id);
+
+    storage::dictionary_put::<Vec<u8>>(seed_uref, &dictionary_item_key, updated_pool);
+}
+
+// retrieve stake pool and validate caller and lock info
+fn update_storage(stake_pool: &StakePool, user_info: UserInfo, pool_id: u64) {
+    is_id_valid(pool_id);
+
+    let new_user_info = UserInfo::pack(&user_info);
+    let updated_pool = StakePool::pack(stake_pool);
+
+    let seed_uref = *runtime::get_key(KEY_NAME_DIC_STAKE_INFOS)
+        .unwrap_or_revert_with(ApiError::User(1))
+        .as_uref()
+        .unwrap_or_revert_with(ApiError::User(2));
+
+    let user_seed_uref = *runtime::get_key(KEY_NAME_USER_INFOS)
+        .unwrap_or_revert_with(ApiError::User(3))
+        .as_uref()
+        .unwrap_or_revert_with(ApiError::User(4));
+
+    let dictionary_item_key = make_pool_key_id(pool_id);
+    let user_info_key = make_user_key_by_id(pool_id);
+
+    storage::dictionary_put::<Vec<u8>>(seed_uref, &dictionary_item_key, updated_pool);
+    storage::dictionary_put::<Vec<u8>>(user_seed_uref, &user_info_key, new_user_info);
+}
+
+// retrieve the unbond of an account in a pool
+fn get_unbond(account: Address, pool_id: u64) -> Unbond {
+    let seed_uref = *runtime::get_key(KEY_NAME_UNBONDS)
+        .unwrap_or_revert()
+        .as_uref()
+        .unwrap_or_revert();
+
+    match storage::dictionary_get::<Vec<u8>>(seed_uref, &make_account_key(account, pool_id))
+        .unwrap_or_revert()
+    {
+        Some(unbond) => Unbond::unpack(unbond),
+        None => Unbond::default(),
+    }
+}
+
+// store the caller's unbond in a pool
+fn save_unbond(pool_id: u64, unbond: &Unbond) {
+    let seed_uref = *runtime::get_key(KEY_NAME_UNBONDS)
+        .unwrap_or_revert()
+        .as_uref()
+        .unwrap_or_revert();
+
+    storage::dictionary_put::<Vec<u8>>(seed_uref, &make_user_key_by_id(pool_id), unbond.pack());
+}
+
+// records the caller's stake and the pool's total
+// stake as of now, for voting power lookups
+fn write_checkpoints(stake_pool: &StakePool, user_info: &UserInfo) {
+    let now: u64 = runtime::get_blocktime().into();
+
+    push_checkpoint(
+        KEY_NAME_USER_CHECKPOINTS,
+        &make_user_key_by_id(stake_pool.id),
+        now,
+        user_info.amount,
+    );
+    push_checkpoint(
+        KEY_NAME_POOL_CHECKPOINTS,
+        &make_pool_key_id(stake_pool.id),
+        now,
+        stake_pool.total_staked,
+    );
+}
+
+fn push_checkpoint(dictionary: &str, owner: &str, timestamp: u64, amount: U256) {
+    checkpoint::Checkpoints::new(dictionary, owner).push(timestamp, amount);
+}
+
+fn read_checkpoint(dictionary: &str, owner: &str, timestamp: u64) -> U256 {
+    checkpoint::Checkpoints::new(dictionary, owner).amount_at(timestamp)
+}
+
+// retrieve user info
+fn get_user_info_for_pool(pool_id: u64) -> UserInfo {
+    get_user_info_for_account(staker(), pool_id)
+}
+
+// retrieve user info of any account
+fn get_user_info_for_account(account: Address, pool_id: u64) -> UserInfo {
+    is_id_valid(pool_id);
+
+    let seed_uref = *runtime::get_key(KEY_NAME_USER_INFOS)
+        .unwrap_or_revert()
+        .as_uref()
+        .unwrap_or_revert();
+
+    let dictionary_item_key = make_account_key(account, pool_id);
+
+    let user_info_bytes_option =
+        storage::dictionary_get::<Vec<u8>>(seed_uref, &dictionary_item_key);
+
+    match user_info_bytes_option.unwrap() {
+        Some(user) => UserInfo::unpack(user),
+        None => UserInfo::default(),
+    }
+}
+
+/*
+get contract hash
+*/
+fn self_contract_hash() -> ContractPackageHash {
+    utils::get_key(KEY_NAME_SELF_CONTRACT_HASH)
+}
+
+/*
+set contract hash - used in token transfer on claim() and lock( ).
+*/
+fn set_self_contract_hash(conthash: ContractPackageHash) {
+    utils::set_key(KEY_NAME_SELF_CONTRACT_HASH, conthash);
+}
+
+/*
+get reward purse of a CSPR pool
+*/
+fn get_reward_purse(pool_id: u64) -> URef {
+    runtime::get_key(&make_reward_purse_key(pool_id))
+        .unwrap_or_revert_with(ApiError::MissingKey)
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant)
+}
+
+/*
+named key of the reward purse for CSPR pools
+*/
+fn make_reward_purse_key(pool_id: u64) -> String {
+    String::from(KEY_NAME_REWARD_PURSE) + &pool_id.to_string()
+}
+
+/*
+for organizing pools by index
+*/
+fn make_pool_key_id(pool_id: u64) -> String {
+    make_key_prefix(self_contract_hash().to_string()) + &pool_id.to_string()
+}
+
+/*
+for organizing pools by index
+*/
+fn make_user_key_by_id(pool_id: u64) -> String {
+    make_account_key(staker(), pool_id)
+}
+
+/*
+for organizing stakes of an account by pool index
+*/
+fn make_account_key(account: Address, pool_id: u64) -> String {
+    make_key_prefix(address_to_string(account)) + &pool_id.to_string()
+}
+
+/*
+hex of the account hash or contract package hash behind an address
+*/
+fn address_to_string(address: Address) -> String {
+    match address {
+        Address::Account(account_hash) => account_hash.to_string(),
+        Address::Contract(contract_package_hash) => contract_package_hash.to_string(),
+    }
+}
+
+/*
+whoever called the contract directly, positions belong to it.
+Contracts like vaults stake under their package hash
+*/
+fn staker() -> Address {
+    utils::get_immediate_caller()
+}
+
+/*
+first 15 characters of a hash, dictionary keys append the pool id to it
+*/
+fn make_key_prefix(hash: String) -> String {
+    let (pre_key, _) = hash.split_at(15);
+
+    String::from(pre_key)
+}
+
+// dictionaries and key prefixes looked up once for
+// all the pools touched by a batch call
+struct Batch {
+    pools: URef,
+    user_infos: URef,
+    pool_key_prefix: String,
+    user_key_prefix: String,
+    index: u64,
+}
+
+impl Batch {
+    fn new() -> Self {
+        Batch {
+            pools: *runtime::get_key(KEY_NAME_DIC_STAKE_INFOS)
+                .unwrap_or_revert_with(ApiError::User(5))
+                .as_uref()
+                .unwrap_or_revert_with(ApiError::User(6)),
+            user_infos: *runtime::get_key(KEY_NAME_USER_INFOS)
+                .unwrap_or_revert_with(ApiError::User(3))
+                .as_uref()
+                .unwrap_or_revert_with(ApiError::User(4)),
+            pool_key_prefix: make_key_prefix(self_contract_hash().to_string()),
+            user_key_prefix: make_key_prefix(address_to_string(staker())),
+            index: utils::get_key(KEY_NAME_INDEX),
+        }
+    }
+
+    fn get_pool(&self, pool_id: u64) -> StakePool {
+        if self.index < pool_id {
+            revert(ApiError::InvalidArgument)
+        }
+
+        let stake_pool_bytes =
+            storage::dictionary_get::<Vec<u8>>(self.pools, &self.pool_key(pool_id))
+                .unwrap_or_revert_with(ApiError::MissingKey)
+                .unwrap_or_revert_with(ApiError::User(7));
+
+        StakePool::unpack(stake_pool_bytes)
+    }
+
+    fn get_user_info(&self, pool_id: u64) -> UserInfo {
+        let user_info_bytes_option =
+            storage::dictionary_get::<Vec<u8>>(self.user_infos, &self.user_key(pool_id));
+
+        match user_info_bytes_option.unwrap_or_revert() {
+            Some(user) => UserInfo::unpack(user),
+            None => UserInfo::default(),
+        }
+    }
+
+    fn save_pool(&self, stake_pool: &StakePool) {
+        storage::dictionary_put::<Vec<u8>>(
+            self.pools,
+            &self.pool_key(stake_pool.id),
+            StakePool::pack(stake_pool),
+        );
+    }
+
+    fn save(&self, stake_pool: &StakePool, user_info: &UserInfo) {
+        self.save_pool(stake_pool);
+
+        storage::dictionary_put::<Vec<u8>>(
+            self.user_infos,
+            &self.user_key(stake_pool.id),
+            UserInfo::pack(user_info),
+        );
+    }
+
+    fn pool_key(&self, pool_id: u64) -> String {
+        self.pool_key_prefix.clone() + &pool_id.to_string()
+    }
+
+    fn user_key(&self, pool_id: u64) -> String {
+        self.user_key_prefix.clone() + &pool_id.to_string()
+    }
+}
+
+// amounts a batch call owes the caller, summed up
+// so every token is paid in a single transfer
+#[derive(Default)]
+struct Payouts {
+    tokens: BTreeMap<ContractHash, U256>,
+    // CSPR rewards are paid from each pool's own purse
+    native_rewards: BTreeMap<u64, U256>,
+}
+
+impl Payouts {
+    fn add_token(&mut self, token: ContractHash, amount: U256) {
+        let total = self.tokens.entry(token).or_insert_with(U256::zero);
+        *total = math::add(*total, amount).unwrap_or_revert();
+    }
+
+    fn add_reward(&mut self, pool: &StakePool, amount: U256) {
+        if pool.native_reward {
+            let total = self
+                .native_rewards
+                .entry(pool.id)
+                .or_insert_with(U256::zero);
+            *total = math::add(*total, amount).unwrap_or_revert();
+        } else {
+            self.add_token(pool.reward_token, amount)
+        }
+    }
+
+    fn pay(self, recipient: Address) {
+        for (token, amount) in self.tokens {
+            if !amount.is_zero() {
+                interact_erc20::default().transfer(token, recipient, amount)
+            }
+        }
+
+        for (pool_id, amount) in self.native_rewards {
+            if !amount.is_zero() {
+                transfer_native_reward(pool_id, recipient, amount)
+            }
+        }
+    }
+}
@@ -1,50 +1,134 @@
-extern crate alloc;
-
-use core::convert::TryInto;
-
-use casper_contract::{
-    contract_api::{runtime, storage},
-    unwrap_or_revert::UnwrapOrRevert,
-};
-
-use alloc::string::String;
-use casper_types::{
-    account::AccountHash,
-    api_error::ApiError,
-    bytesrepr::{FromBytes, ToBytes},
-    CLTyped, URef,
-};
-
-pub fn get_key<T: FromBytes + CLTyped>(name: &str) -> T {
-    let key: URef = runtime::get_key(name)
-        .unwrap_or_revert_with(ApiError::MissingKey)
-        .into_uref()
-        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
-    storage::read(key)
-        .unwrap_or_revert_with(ApiError::Read)
-        .unwrap_or_revert_with(ApiError::ValueNotFound)
-}
-
-pub fn set_key<T: ToBytes + CLTyped>(name: &str, value: T) {
-    match runtime::get_key(name) {
-        Some(key) => {
-            let key_ref = key.try_into().unwrap_or_revert();
-            storage::write(key_ref, value);
-        }
-        None => {
-            let key = storage::new_uref(value).into();
-            runtime::put_key(name, key);
-        }
-    }
-}
-
-pub fn get_account_hash_from_string(str_hash: String) -> AccountHash {
-    let ac_hashex = AccountHash::from_bytes(str_hash.as_bytes());
-
-    let (ac_hash, _) = match ac_hashex {
-        Ok(ac_hash) => ac_hash,
-        Err(erro) => panic!("Problem opening {:?}", erro),
-    };
-
-    ac_hash
-}
+extern crate alloc;
+
+use core::convert::TryInto;
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    ext_ffi,
+    unwrap_or_revert::UnwrapOrRevert,
+};
+
+use alloc::{string::String, vec};
+use casper_types::{
+    account::AccountHash,
+    api_error::{self, ApiError},
+    bytesrepr::{self, FromBytes, ToBytes},
+    system::CallStackElement,
+    CLTyped, URef, U256, U512,
+};
+
+use crate::{constants::KEY_NAME_REENTRANCY_LOCK, Address, Error};
+
+pub fn get_key<T: FromBytes + CLTyped>(name: &str) -> T {
+    let key: URef = runtime::get_key(name)
+        .unwrap_or_revert_with(ApiError::MissingKey)
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+    storage::read(key)
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_revert_with(ApiError::ValueNotFound)
+}
+
+pub fn set_key<T: ToBytes + CLTyped>(name: &str, value: T) {
+    match runtime::get_key(name) {
+        Some(key) => {
+            let key_ref = key.try_into().unwrap_or_revert();
+            storage::write(key_ref, value);
+        }
+        None => {
+            let key = storage::new_uref(value).into();
+            runtime::put_key(name, key);
+        }
+    }
+}
+
+// contract-wide reentrancy lock, mutating entry points hold it
+// while they run so token contracts can't call back into them
+pub struct ReentrancyGuard;
+
+impl ReentrancyGuard {
+    pub fn enter() -> Self {
+        if get_key::<bool>(KEY_NAME_REENTRANCY_LOCK) {
+            runtime::revert(Error::Reentrant)
+        }
+
+        set_key(KEY_NAME_REENTRANCY_LOCK, true);
+
+        ReentrancyGuard
+    }
+}
+
+impl Drop for ReentrancyGuard {
+    // released when the entry point returns, a revert
+    // discards the lock along with everything else
+    fn drop(&mut self) {
+        set_key(KEY_NAME_REENTRANCY_LOCK, false);
+    }
+}
+
+// like runtime::get_named_arg, but returns None
+// instead of reverting when the arg wasn't passed
+pub fn get_optional_named_arg<T: FromBytes>(name: &str) -> Option<T> {
+    let mut arg_size: usize = 0;
+    let ret = unsafe {
+        ext_ffi::casper_get_named_arg_size(
+            name.as_bytes().as_ptr(),
+            name.len(),
+            &mut arg_size as *mut usize,
+        )
+    };
+
+    match api_error::result_from(ret) {
+        Ok(_) => {}
+        Err(ApiError::MissingArgument) => return None,
+        Err(e) => runtime::revert(e),
+    }
+
+    let mut arg_bytes = vec![0u8; arg_size];
+    if arg_size > 0 {
+        let ret = unsafe {
+            ext_ffi::casper_get_named_arg(
+                name.as_bytes().as_ptr(),
+                name.len(),
+                arg_bytes.as_mut_ptr(),
+                arg_size,
+            )
+        };
+        api_error::result_from(ret).unwrap_or_revert();
+    }
+
+    Some(bytesrepr::deserialize(arg_bytes).unwrap_or_revert_with(ApiError::InvalidArgument))
+}
+
+// the account or contract package that called the current
+// contract directly, unlike runtime::get_caller which is
+// always the account that sent the deploy
+pub fn get_immediate_caller() -> Address {
+    match runtime::get_call_stack().into_iter().rev().nth(1) {
+        Some(CallStackElement::Session { account_hash }) => Address::from(account_hash),
+        Some(CallStackElement::StoredSession { account_hash, .. }) => Address::from(account_hash),
+        Some(CallStackElement::StoredContract {
+            contract_package_hash,
+            ..
+        }) => Address::from(contract_package_hash),
+        None => runtime::revert(Error::InvalidContext),
+    }
+}
+
+pub fn get_account_hash_from_string(str_hash: String) -> AccountHash {
+    let ac_hashex = AccountHash::from_bytes(str_hash.as_bytes());
+
+    let (ac_hash, _) = match ac_hashex {
+        Ok(ac_hash) => ac_hash,
+        Err(erro) => panic!("Problem opening {:?}", erro),
+    };
+
+    ac_hash
+}
+
+// widens a token amount to motes for system transfers
+pub fn u256_to_u512(value: U256) -> U512 {
+    let mut bytes = [0_u8; 32];
+    value.to_little_endian(&mut bytes);
+    U512::from_little_endian(&bytes)
+}
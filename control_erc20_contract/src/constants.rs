@@ -1,56 +1,89 @@
-//! Constants used by the ERC20 contract.
-
-pub const CONTRACT_NAME: &str = "control-erc20";
-pub const CONTRACT_VERSION: &str = "CONT_VERSION_KEY";
-pub const CONTRACT_HASH: &str = "erc20_conthash";
-
-pub const KEY_NAME_ADMIN: &str = "admin-account";
-pub const KEY_NAME_INDEX: &str = "index";
-pub const KEY_NAME_TOTAL_LOCK_AMOUNT: &str = "total_lock_amount";
-pub const KEY_NAME_SELF_CONTRACT_HASH: &str = "self_contract_hash";
-pub const KEY_NAME_DIC_LOCK_INFOS: &str = "dic_locker_infos";
-pub const KEY_NAME_INITIALIZED: &str = "initialized";
-pub const KEY_NAME_PAUSED: &str = "paused";
-
-
-pub const VESTOR_PACKAGE_NAME: &str = "vestor_pack";
-pub const VESTOR_UREF_NAME: &str = "vestor_access_uref";
-
-pub const ARG_NAME_ERC20_TOKEN_HASH: &str = "token-hash";
-pub const ARG_NAME_ERC20_SELFCONTRACT_HASH: &str = "package-hash";
-
-pub const ARG_NAME_CLIFF_DURTIME: &str = "cliff_durtime";
-pub const ARG_NAME_NEW_ADMIN: &str = "new_admin";
-
-pub const ARG_NAME_CLIFF_DURATION: &str = "cliff_duration";
-pub const ARG_NAME_LOCK_ID: &str = "lock_id";
-pub const ARG_NAME_INDEX: &str = "index";
-pub const ARG_NAME_LOCK_SCHEDULES: &str = "lock_schedules";
-pub const ARG_NAME_NUM_UNLOCKS: &str = "num_locks";
-pub const ARG_NAME_TIME_BETWEEN_LOCKS: &str = "time_between_locks";
-
-pub const ARG_NAME_CLIFF_AMOUNT: &str = "cliff_amount";
-pub const ARG_NAME_UNIT_TIME: &str = "unit_time";
-pub const ARG_NAME_RECIPIENT: &str = "recipient";
-pub const ARG_NAME_ID_VEST: &str = "vest_id";
-pub const ARG_NAME_NEW_OWNER: &str = "new_owner";
-pub const ARG_NAME_NEW_RELEASE_TIME: &str = "new_release_time";
-
-pub const ENTRY_POINT_NAME_INIT: &str = "init";
-pub const ENTRY_POINT_NAME_LOCK: &str = "lock";
-pub const ENTRY_POINT_NAME_CLAIM: &str = "claim";
-pub const ENTRY_POINT_NAME_TOTAL_LOCK_AMOUNT: &str = "total_lock_amount";
-pub const ENTRY_POINT_NAME_TRANSFER_LOCK: &str = "transfer_lock";
-pub const ENTRY_POINT_NAME_EXTEND_LOCK: &str = "extend_lock";
-pub const ENTRY_POINT_NAME_SET_ADMIN: &str = "set_admin";
-pub const ENTRY_POINT_NAME_SET_PAUSE_CONTRACT: &str = "set_pause_contract";
-
-
-pub const KEY_NAME_TOKEN_HASH: &str = "token_hash";
-
-pub const ENTRY_POINT_NAME_BALANCE_OF: &str = "balance_of";
-pub const ENTRY_POINT_NAME_TRANSFER_FROM: &str = "transfer_from";
-pub const ENTRY_POINT_NAME_TRANSFER: &str = "transfer";
-pub const ARG_NAME_OWNER: &str = "owner";
-pub const ARG_NAME_SPENDER: &str = "spender";
-pub const ARG_NAME_AMOUNT: &str = "amount";
+//! Constants used by the ERC20 contract.
+
+pub const CONTRACT_NAME: &str = "control-erc20";
+pub const CONTRACT_VERSION: &str = "CONT_VERSION_KEY";
+pub const CONTRACT_HASH: &str = "erc20_conthash";
+
+pub const KEY_NAME_ADMIN: &str = "admin-account";
+pub const KEY_NAME_INDEX: &str = "index";
+pub const KEY_NAME_TOTAL_LOCK_AMOUNT: &str = "total_lock_amount";
+pub const KEY_NAME_SELF_CONTRACT_HASH: &str = "self_contract_hash";
+pub const KEY_NAME_DIC_LOCK_INFOS: &str = "dic_locker_infos";
+pub const KEY_NAME_INITIALIZED: &str = "initialized";
+pub const KEY_NAME_PAUSED: &str = "paused";
+pub const KEY_NAME_REENTRANCY_LOCK: &str = "reentrancy_lock";
+pub const KEY_NAME_STAKE_CONTRACT: &str = "stake_contract";
+pub const KEY_NAME_STAKE_PACKAGE: &str = "stake_package";
+pub const KEY_NAME_DIC_DELEGATIONS: &str = "dic_delegations";
+pub const KEY_NAME_DIC_DELEGATION_POOLS: &str = "dic_delegation_pools";
+pub const KEY_NAME_DIC_LINEAR_VESTS: &str = "dic_linear_vests";
+
+
+pub const VESTOR_PACKAGE_NAME: &str = "vestor_pack";
+pub const VESTOR_UREF_NAME: &str = "vestor_access_uref";
+
+pub const ARG_NAME_ERC20_TOKEN_HASH: &str = "token-hash";
+pub const ARG_NAME_ERC20_SELFCONTRACT_HASH: &str = "package-hash";
+
+pub const ARG_NAME_CLIFF_DURTIME: &str = "cliff_durtime";
+pub const ARG_NAME_NEW_ADMIN: &str = "new_admin";
+
+pub const ARG_NAME_CLIFF_DURATION: &str = "cliff_duration";
+pub const ARG_NAME_LOCK_ID: &str = "lock_id";
+pub const ARG_NAME_INDEX: &str = "index";
+pub const ARG_NAME_LOCK_SCHEDULES: &str = "lock_schedules";
+pub const ARG_NAME_NUM_UNLOCKS: &str = "num_locks";
+pub const ARG_NAME_TIME_BETWEEN_LOCKS: &str = "time_between_locks";
+
+pub const ARG_NAME_CLIFF_AMOUNT: &str = "cliff_amount";
+pub const ARG_NAME_UNIT_TIME: &str = "unit_time";
+pub const ARG_NAME_RECIPIENT: &str = "recipient";
+pub const ARG_NAME_ID_VEST: &str = "vest_id";
+pub const ARG_NAME_NEW_OWNER: &str = "new_owner";
+pub const ARG_NAME_NEW_RELEASE_TIME: &str = "new_release_time";
+pub const ARG_NAME_STAKE_CONTRACT: &str = "stake_contract";
+pub const ARG_NAME_STAKE_PACKAGE: &str = "stake_package";
+pub const ARG_NAME_POOL_ID: &str = "pool_id";
+pub const ARG_NAME_POOL_IDS: &str = "pool_ids";
+pub const ARG_NAME_START_TIME: &str = "start_time";
+pub const ARG_NAME_END_TIME: &str = "end_time";
+pub const ARG_NAME_REVOCABLE: &str = "revocable";
+
+pub const ENTRY_POINT_NAME_INIT: &str = "init";
+pub const ENTRY_POINT_NAME_LOCK: &str = "lock";
+pub const ENTRY_POINT_NAME_LOCK_FOR: &str = "lock_for";
+pub const ENTRY_POINT_NAME_LOCK_CUSTOM: &str = "lock_custom";
+pub const ENTRY_POINT_NAME_LOCK_LINEAR: &str = "lock_linear";
+pub const ENTRY_POINT_NAME_CLAIM: &str = "claim";
+pub const ENTRY_POINT_NAME_REVOKE: &str = "revoke";
+pub const ENTRY_POINT_NAME_TOTAL_LOCK_AMOUNT: &str = "total_lock_amount";
+pub const ENTRY_POINT_NAME_TRANSFER_LOCK: &str = "transfer_lock";
+pub const ENTRY_POINT_NAME_EXTEND_LOCK: &str = "extend_lock";
+pub const ENTRY_POINT_NAME_SET_ADMIN: &str = "set_admin";
+pub const ENTRY_POINT_NAME_SET_PAUSE_CONTRACT: &str = "set_pause_contract";
+pub const ENTRY_POINT_NAME_SET_STAKE_CONTRACT: &str = "set_stake_contract";
+pub const ENTRY_POINT_NAME_DELEGATE_LOCK: &str = "delegate_lock";
+pub const ENTRY_POINT_NAME_UNDELEGATE_LOCK: &str = "undelegate_lock";
+pub const ENTRY_POINT_NAME_HARVEST_LOCK: &str = "harvest_lock";
+pub const ENTRY_POINT_NAME_EXIT_POOL: &str = "exit_pool";
+
+
+pub const KEY_NAME_TOKEN_HASH: &str = "token_hash";
+
+pub const ENTRY_POINT_NAME_BALANCE_OF: &str = "balance_of";
+pub const ENTRY_POINT_NAME_TRANSFER_FROM: &str = "transfer_from";
+pub const ENTRY_POINT_NAME_TRANSFER: &str = "transfer";
+pub const ENTRY_POINT_NAME_APPROVE: &str = "approve";
+pub const ARG_NAME_OWNER: &str = "owner";
+pub const ARG_NAME_SPENDER: &str = "spender";
+pub const ARG_NAME_AMOUNT: &str = "amount";
+
+// entry points of the staking contract
+pub const ENTRY_POINT_NAME_STAKE_DEPOSIT: &str = "deposit";
+pub const ENTRY_POINT_NAME_STAKE_WITHDRAW: &str = "withdraw";
+pub const ENTRY_POINT_NAME_HARVEST_MANY: &str = "harvest_many";
+pub const ENTRY_POINT_NAME_EMERGENCY_WITHDRAW: &str = "emergency_withdraw";
+pub const ENTRY_POINT_NAME_GET_POOL: &str = "get_pool";
+
+// scales the reward per delegated token
+pub const DELEGATION_PRECISION: u64 = 1_000_000_000_000;
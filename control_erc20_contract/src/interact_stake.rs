@@ -0,0 +1,92 @@
+#![allow(non_camel_case_types)]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
+
+use casper_contract::contract_api::runtime;
+use casper_types::{runtime_args, ContractHash, Key, RuntimeArgs, U256};
+
+use crate::constants::{
+    ARG_NAME_AMOUNT, ARG_NAME_POOL_ID, ARG_NAME_POOL_IDS, ENTRY_POINT_NAME_EMERGENCY_WITHDRAW,
+    ENTRY_POINT_NAME_GET_POOL, ENTRY_POINT_NAME_HARVEST_MANY, ENTRY_POINT_NAME_STAKE_DEPOSIT,
+    ENTRY_POINT_NAME_STAKE_WITHDRAW,
+};
+
+/*
+calls into the staking contract, delegated locks
+are staked under the vesting contract's package hash
+*/
+#[derive(Default)]
+pub struct interact_stake;
+
+impl interact_stake {
+    // returns the amount added to the vesting contract's stake
+    pub fn deposit(&self, stake_contract: ContractHash, pool_id: u64, amount: U256) -> U256 {
+        runtime::call_contract(
+            stake_contract,
+            ENTRY_POINT_NAME_STAKE_DEPOSIT,
+            runtime_args! {
+                ARG_NAME_AMOUNT => amount,
+                ARG_NAME_POOL_ID => pool_id
+            },
+        )
+    }
+
+    // returns the amount withdrawn and the rewards paid
+    pub fn withdraw(
+        &self,
+        stake_contract: ContractHash,
+        pool_id: u64,
+        amount: U256,
+    ) -> (U256, U256) {
+        runtime::call_contract(
+            stake_contract,
+            ENTRY_POINT_NAME_STAKE_WITHDRAW,
+            runtime_args! {
+                ARG_NAME_AMOUNT => amount,
+                ARG_NAME_POOL_ID => pool_id
+            },
+        )
+    }
+
+    // withdraws the whole stake of a pool in emergency
+    // mode without rewards, returns the amount withdrawn
+    pub fn emergency_withdraw(&self, stake_contract: ContractHash, pool_id: u64) -> U256 {
+        runtime::call_contract(
+            stake_contract,
+            ENTRY_POINT_NAME_EMERGENCY_WITHDRAW,
+            runtime_args! {
+                ARG_NAME_POOL_ID => pool_id
+            },
+        )
+    }
+
+    // claims the pending rewards of the pool, returns the amount paid
+    pub fn harvest(&self, stake_contract: ContractHash, pool_id: u64) -> U256 {
+        let rewards: Vec<U256> = runtime::call_contract(
+            stake_contract,
+            ENTRY_POINT_NAME_HARVEST_MANY,
+            runtime_args! {
+                ARG_NAME_POOL_IDS => vec![pool_id]
+            },
+        );
+
+        rewards[0]
+    }
+
+    // pool fields by name, with rewards accrued up to now
+    pub fn get_pool(
+        &self,
+        stake_contract: ContractHash,
+        pool_id: u64,
+    ) -> (BTreeMap<String, Key>, BTreeMap<String, U256>) {
+        runtime::call_contract(
+            stake_contract,
+            ENTRY_POINT_NAME_GET_POOL,
+            runtime_args! {
+                ARG_NAME_POOL_ID => pool_id
+            },
+        )
+    }
+}
@@ -1,54 +1,66 @@
-#![cfg_attr(not(feature = "std"), no_std)]
-
-pub mod constants;
-pub mod interact_token;
-pub mod utils;
-
-mod vest;
-pub use vest::VestContract;
-
-mod address;
-pub use address::Address;
-
-#[derive(PartialEq, Debug)]
-pub enum VestingError {
-    NotEnoughBalance,
-    AdminReleaseErrorNotPaused,
-    AdminReleaseErrorNothingToWithdraw,
-    AdminReleaseErrorNotEnoughTimeElapsed,
-    AlreadyPaused,
-    AlreadyUnpaused,
-}
-
-#[derive(PartialEq, Debug)]
-// #[derive(Error, Debug)]
-pub enum VestError {
-    AlreadyPaused = 1,
-    AlreadyUnpaused = 2,
-    NotTheAdminAccount = 3,
-    NotTheRecipientAccount = 4,
-    UnexpectedVestingError = 5,
-    NotEnoughBalance = 6,
-    PurseTransferError = 7,
-    NotPaused = 8,
-    NothingToWithdraw = 9,
-    NotEnoughTimeElapsed = 10,
-    LocalPurseKeyMissing = 11,
-    UnexpectedType = 12,
-    MissingKey = 13,
-    Paused = 14
-}
-
-#[derive(PartialEq, Debug)]
-pub enum Error {
-    /// ERC20 contract called from within an invalid context.
-    InvalidContext,
-    /// Spender does not have enough balance.
-    InsufficientBalance,
-    /// Spender does not have enough allowance approved.
-    InsufficientAllowance,
-    /// Operation would cause an integer overflow.
-    Overflow,
-    /// User error.
-    User(u16),
-}
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod constants;
+pub mod interact_stake;
+pub mod interact_token;
+pub mod utils;
+
+mod vest;
+pub use vest::VestContract;
+
+mod address;
+pub use address::Address;
+
+use casper_types::ApiError;
+
+#[derive(PartialEq, Debug)]
+pub enum VestingError {
+    NotEnoughBalance,
+    AdminReleaseErrorNotPaused,
+    AdminReleaseErrorNothingToWithdraw,
+    AdminReleaseErrorNotEnoughTimeElapsed,
+    AlreadyPaused,
+    AlreadyUnpaused,
+}
+
+#[derive(PartialEq, Debug)]
+// #[derive(Error, Debug)]
+pub enum VestError {
+    AlreadyPaused = 1,
+    AlreadyUnpaused = 2,
+    NotTheAdminAccount = 3,
+    NotTheRecipientAccount = 4,
+    UnexpectedVestingError = 5,
+    NotEnoughBalance = 6,
+    PurseTransferError = 7,
+    NotPaused = 8,
+    NothingToWithdraw = 9,
+    NotEnoughTimeElapsed = 10,
+    LocalPurseKeyMissing = 11,
+    UnexpectedType = 12,
+    MissingKey = 13,
+    Paused = 14,
+    Reentrant = 15,
+    Overflow = 16,
+    Underflow = 17
+}
+
+impl From<VestError> for ApiError {
+    fn from(error: VestError) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Error {
+    /// ERC20 contract called from within an invalid context.
+    InvalidContext,
+    /// Spender does not have enough balance.
+    InsufficientBalance,
+    /// Spender does not have enough allowance approved.
+    InsufficientAllowance,
+    /// Operation would cause an integer overflow.
+    Overflow,
+    /// User error.
+    User(u16),
+}
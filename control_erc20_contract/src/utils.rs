@@ -1,50 +1,76 @@
-extern crate alloc;
-
-use core::convert::TryInto;
-
-use casper_contract::{
-    contract_api::{runtime, storage},
-    unwrap_or_revert::UnwrapOrRevert,
-};
-
-use alloc::string::String;
-use casper_types::{
-    account::AccountHash,
-    api_error::ApiError,
-    bytesrepr::{FromBytes, ToBytes},
-    CLTyped, URef,
-};
-
-pub fn get_key<T: FromBytes + CLTyped>(name: &str) -> T {
-    let key: URef = runtime::get_key(name)
-        .unwrap_or_revert_with(ApiError::MissingKey)
-        .into_uref()
-        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
-    storage::read(key)
-        .unwrap_or_revert_with(ApiError::Read)
-        .unwrap_or_revert_with(ApiError::ValueNotFound)
-}
-
-pub fn set_key<T: ToBytes + CLTyped>(name: &str, value: T) {
-    match runtime::get_key(name) {
-        Some(key) => {
-            let key_ref = key.try_into().unwrap_or_revert();
-            storage::write(key_ref, value);
-        }
-        None => {
-            let key = storage::new_uref(value).into();
-            runtime::put_key(name, key);
-        }
-    }
-}
-
-pub fn get_account_hash_from_string(str_hash: String) -> AccountHash {
-    let ac_hashex = AccountHash::from_bytes(str_hash.as_bytes());
-
-    let (ac_hash, _) = match ac_hashex {
-        Ok(ac_hash) => ac_hash,
-        Err(erro) => panic!("Problem opening {:?}", erro),
-    };
-
-    ac_hash
-}
+extern crate alloc;
+
+use core::convert::TryInto;
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+
+use alloc::string::String;
+use casper_types::{
+    account::AccountHash,
+    api_error::ApiError,
+    bytesrepr::{FromBytes, ToBytes},
+    CLTyped, URef,
+};
+
+use crate::{constants::KEY_NAME_REENTRANCY_LOCK, VestError};
+
+pub fn get_key<T: FromBytes + CLTyped>(name: &str) -> T {
+    let key: URef = runtime::get_key(name)
+        .unwrap_or_revert_with(ApiError::MissingKey)
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+    storage::read(key)
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_revert_with(ApiError::ValueNotFound)
+}
+
+pub fn set_key<T: ToBytes + CLTyped>(name: &str, value: T) {
+    match runtime::get_key(name) {
+        Some(key) => {
+            let key_ref = key.try_into().unwrap_or_revert();
+            storage::write(key_ref, value);
+        }
+        None => {
+            let key = storage::new_uref(value).into();
+            runtime::put_key(name, key);
+        }
+    }
+}
+
+// contract-wide reentrancy lock, mutating entry points hold it
+// while they run so token contracts can't call back into them
+pub struct ReentrancyGuard;
+
+impl ReentrancyGuard {
+    pub fn enter() -> Self {
+        if get_key::<bool>(KEY_NAME_REENTRANCY_LOCK) {
+            runtime::revert(VestError::Reentrant)
+        }
+
+        set_key(KEY_NAME_REENTRANCY_LOCK, true);
+
+        ReentrancyGuard
+    }
+}
+
+impl Drop for ReentrancyGuard {
+    // released when the entry point returns, a revert
+    // discards the lock along with everything else
+    fn drop(&mut self) {
+        set_key(KEY_NAME_REENTRANCY_LOCK, false);
+    }
+}
+
+pub fn get_account_hash_from_string(str_hash: String) -> AccountHash {
+    let ac_hashex = AccountHash::from_bytes(str_hash.as_bytes());
+
+    let (ac_hash, _) = match ac_hashex {
+        Ok(ac_hash) => ac_hash,
+        Err(erro) => panic!("Problem opening {:?}", erro),
+    };
+
+    ac_hash
+}
@@ -1,549 +1,1430 @@
-extern crate alloc;
-
-use core::convert::TryInto;
-
-use alloc::{
-    string::{String, ToString},
-    vec::Vec,
-};
-
-use casper_contract::{
-    contract_api::{
-        runtime::{self, revert},
-        storage,
-    },
-    unwrap_or_revert::UnwrapOrRevert,
-};
-
-use casper_types::{
-    account::AccountHash, bytesrepr::ToBytes, ApiError, ContractHash, ContractPackageHash, URef,
-    U256,
-};
-
-use crate::{constants::KEY_NAME_INDEX, utils};
-use crate::{constants::KEY_NAME_INITIALIZED, interact_token::interact_erc20};
-
-use crate::constants::{
-    KEY_NAME_ADMIN, KEY_NAME_DIC_LOCK_INFOS, KEY_NAME_PAUSED, KEY_NAME_SELF_CONTRACT_HASH,
-};
-
-#[derive(Default)]
-pub struct VestContract;
-
-#[derive(Clone, Copy)]
-pub struct LockSchedule {
-    // 8
-    release: u64,
-    // 32
-    amount: U256,
-}
-
-pub struct VestInfo {
-    // 8 : 8
-    id: u64,
-    // 8 : 16
-    lock_time: u64,
-    // 32 : 48
-    recipient: AccountHash,
-    // 32 : 80
-    token_hash: ContractHash,
-    // 40 : (80 + 40(num))
-    schedules: Vec<LockSchedule>,
-}
-
-impl VestInfo {
-    // returns all lock info as a byte array
-    fn pack(&self) -> Vec<u8> {
-        let id_bytes = self.id.into_bytes().unwrap_or_revert();
-        let lock_time_bytes = self.lock_time.into_bytes().unwrap_or_revert();
-        let recipient_bytes = self.recipient.as_bytes();
-        let token_hash_bytes = self.token_hash.value();
-
-        let mut res: Vec<u8> = Vec::new();
-
-        for i in id_bytes {
-            res.push(i);
-        }
-
-        for i in 8..16 {
-            res.push(lock_time_bytes[i - 8])
-        }
-
-        for i in 16..48 {
-            res.push(recipient_bytes[i - 16])
-        }
-
-        for i in 48..80 {
-            res.push(token_hash_bytes[i - 48])
-        }
-
-        let mut counter = 0;
-
-        while counter < self.schedules.len() {
-            let slice_to_pack = &self.schedules[counter];
-            let schedule_bytes = Self::pack_schedule(slice_to_pack);
-            for i in schedule_bytes {
-                res.push(i);
-            }
-            counter += 1
-        }
-
-        res
-    }
-
-    fn pack_schedule(src: &LockSchedule) -> [u8; 40] {
-        let mut res = [0u8; 40];
-
-        let mut little_endian_bytes = [0_u8; 64];
-
-        src.amount.to_little_endian(&mut little_endian_bytes[..32]);
-
-        let release = src.release.to_le_bytes();
-
-        res[..8].copy_from_slice(&release[..8]);
-
-        res[8..40].copy_from_slice(&little_endian_bytes[..32]);
-
-        res
-    }
-
-    fn unpack(src: Vec<u8>) -> Self {
-        let id = u64::from_le_bytes(src[0..8].try_into().unwrap());
-        let lock_time = u64::from_le_bytes(src[8..16].try_into().unwrap());
-        let recipient: AccountHash = src[16..48].try_into().unwrap();
-        let token_hash: ContractHash = src[48..80].try_into().unwrap();
-
-        let (_, slice_schedules) = src.split_at(80);
-
-        let schedules = Self::unpack_all_schedules(slice_schedules.to_vec());
-
-        Self {
-            id,
-            lock_time,
-            recipient,
-            token_hash,
-            schedules,
-        }
-    }
-
-    fn unpack_schedule(src: &[u8]) -> LockSchedule {
-        let release = u64::from_le_bytes(src[0..8].try_into().unwrap());
-        let amount = U256::from_little_endian(&src[8..40]);
-
-        LockSchedule { release, amount }
-    }
-
-    fn unpack_all_schedules(src: Vec<u8>) -> Vec<LockSchedule> {
-        if src.len() % 40 != 0 {
-            revert(ApiError::Formatting)
-        }
-
-        let mut schedules: Vec<LockSchedule> = Vec::new();
-
-        let mut counter = 0;
-        let total_schedules = src.len().checked_div(40).unwrap_or_revert();
-
-        while counter < total_schedules {
-            let offset = counter.checked_mul(40).unwrap_or_revert();
-            let end_idx = offset.checked_add(40).unwrap_or_revert();
-            let s: LockSchedule = Self::unpack_schedule(&src[offset..end_idx]);
-            schedules.push(s);
-            counter += 1
-        }
-        schedules
-    }
-
-    // gets the recipient account from LockInfo
-    // used in caller_is_recipient() function
-    fn unpack_recipient(src: &[u8]) -> [u8; 32] {
-        let recipient: [u8; 32] = src[16..48].try_into().unwrap();
-        recipient
-    }
-
-    fn clear_entry() -> Vec<u8> {
-        let clear_entry = [0_u8; 0];
-        clear_entry.to_vec()
-    }
-}
-
-impl VestContract {
-    // init to create dictionary for lock info
-    pub fn init(&self, conthash: String) {
-        let is_initialized: bool = utils::get_key(KEY_NAME_INITIALIZED);
-
-        if is_initialized {
-            revert(ApiError::PermissionDenied)
-        }
-
-        storage::new_dictionary(KEY_NAME_DIC_LOCK_INFOS).unwrap_or_revert();
-
-        set_self_contract_hash(conthash);
-
-        utils::set_key(KEY_NAME_INDEX, 0u64);
-        utils::set_key(KEY_NAME_INITIALIZED, true);
-    }
-
-    // @tokh - token to lock
-    // @cliff_amount - amount to lock
-    // @cliff_durtime - time from now until first unlock
-    // @time_between_locks - seconds between each unlock
-    // @number_of_locks - number of locks
-    pub fn lock(
-        &self,
-        tokh: ContractHash,
-        cliff_amount: U256,
-        cliff_durtime: u64,
-        time_between_locks: u64,
-        number_of_locks: u32,
-    ) {
-        if is_paused() {
-            revert(ApiError::User(14))
-        }
-        let seed_uref = *runtime::get_key(KEY_NAME_DIC_LOCK_INFOS)
-            .unwrap_or_revert()
-            .as_uref()
-            .unwrap_or_revert();
-
-        let mut schedules: Vec<LockSchedule> = Vec::new();
-
-        let timestamp: u64 = runtime::get_blocktime().into();
-
-        let first_unlock: u64 = timestamp.checked_add(cliff_durtime).unwrap_or_revert();
-
-        if number_of_locks == 0 {
-            revert(ApiError::InvalidArgument)
-        }
-
-        while schedules.len() < number_of_locks as usize {
-            let release = first_unlock
-                + (time_between_locks
-                    .checked_mul(schedules.len() as u64)
-                    .unwrap_or_revert());
-            let amount = cliff_amount / U256::from(number_of_locks);
-            let s = LockSchedule { release, amount };
-            schedules.push(s)
-        }
-
-        // lock_id
-        let current_index: u64 = utils::get_key(KEY_NAME_INDEX);
-
-        let dictionary_item_key = make_lock_key_id(current_index);
-
-        let info: VestInfo = VestInfo {
-            id: current_index,
-            lock_time: timestamp,
-            recipient: runtime::get_caller(),
-            token_hash: tokh,
-            schedules,
-        };
-
-        // pack lock information into u8 array
-        let res = VestInfo::pack(&info);
-
-        // transfer tokens to this contract
-        let tx = interact_erc20::default().transfer_from(
-            tokh,
-            runtime::get_caller(),
-            self_contract_hash(),
-            cliff_amount,
-        );
-
-        if Some(tx).is_none() {
-            revert(ApiError::None)
-        }
-
-        // This identifies an item within the dictionary
-        // and either creates or updates the associated value.
-        update_storage(
-            seed_uref,
-            dictionary_item_key,
-            res,
-            current_index,
-            runtime::get_caller().to_string(),
-        );
-
-        // update the global counter for lock_id
-        utils::set_key(KEY_NAME_INDEX, current_index + 1u64);
-    }
-
-    // @lock_id = the lock to change
-    // @index = index of the lock to update
-    // @new_release = new release time
-    pub fn extend_lock(&mut self, lock_id: u64, index: u32, new_release: u64) {
-        if is_paused() {
-            revert(ApiError::User(14))
-        }
-        let (mut lock, seed_uref, dictionary_item_key) = get_lock(lock_id);
-
-        // can't update non-existant schedule
-        if index as usize >= lock.schedules.len() {
-            revert(ApiError::InvalidArgument)
-        }
-
-        if lock.schedules[index as usize].release > new_release {
-            revert(ApiError::None)
-        }
-
-        lock.schedules[index as usize].release = new_release;
-
-        // pack updated lock information into u8 array
-        let res = VestInfo::pack(&lock);
-
-        // update key
-        storage::dictionary_put(seed_uref, &dictionary_item_key, res)
-    }
-
-    // @lock_id = the lock to change
-    // @new_owner = who to transfer to
-    pub fn transfer_lock(&mut self, lock_id: u64, new_owner: AccountHash) {
-        if is_paused() {
-            revert(ApiError::User(14))
-        }
-        let (mut lock, seed_uref, dictionary_item_key) = get_lock(lock_id);
-
-        lock.recipient = new_owner;
-
-        // pack lock information into u8 array
-        let res = VestInfo::pack(&lock);
-
-        update_storage_transfer_lock(
-            seed_uref,
-            dictionary_item_key,
-            lock.id,
-            runtime::get_caller().to_string(),
-            new_owner.to_string(),
-            res,
-        )
-    }
-
-    // unlock tokens
-    // @lock_id - the lock to change
-    pub fn claim(&self, lock_id: u64) {
-        if is_paused() {
-            revert(ApiError::User(14))
-        }
-        let (mut lock, seed_uref, dictionary_item_key) = get_lock(lock_id);
-
-        let timestamp: u64 = runtime::get_blocktime().into();
-
-        let mut amount_to_transfer = U256::zero();
-
-        for s in lock.schedules.iter_mut() {
-            if timestamp >= s.release {
-                amount_to_transfer += s.amount;
-                s.amount = U256::zero()
-            }
-        }
-
-        if amount_to_transfer.is_zero() {
-            revert(ApiError::User(9))
-        }
-
-        // transfer tokens from this contract
-        let tx = interact_erc20::default().transfer(
-            lock.token_hash,
-            runtime::get_caller(),
-            amount_to_transfer,
-        );
-
-        if Some(tx).is_none() {
-            revert(ApiError::None)
-        }
-
-        let mut empty_locks: usize = 0;
-        let total_locks = lock.schedules.len();
-
-        for s in lock.schedules.iter() {
-            if s.amount.is_zero() {
-                empty_locks += 1
-            }
-        }
-
-        let all_claimed: bool = total_locks == empty_locks;
-
-        match all_claimed {
-            false => {
-                // pack lock information into u8 array
-                let res = VestInfo::pack(&lock);
-                // update key
-                storage::dictionary_put(seed_uref, &dictionary_item_key, res)
-            }
-            _ => {
-                // clear the entry if it is finished
-                storage::dictionary_put(seed_uref, &dictionary_item_key, VestInfo::clear_entry());
-                let mut id_arr: Vec<u64> =
-                    utils::get_key(runtime::get_caller().to_string().as_str());
-                let idx = id_arr.iter().position(|x| *x == lock_id).unwrap();
-                id_arr.swap_remove(idx);
-                utils::set_key(runtime::get_caller().to_string().as_str(), id_arr);
-            }
-        }
-    }
-
-    // sets new admin
-    // @account - account hash of the new admin
-    // ADMIN ONLY
-    pub fn set_admin(&self, account: AccountHash) {
-        let admin = utils::get_key(KEY_NAME_ADMIN);
-
-        if runtime::get_caller() != admin {
-            revert(ApiError::InvalidPurse)
-        }
-
-        utils::set_key(KEY_NAME_ADMIN, account)
-    }
-
-    // pause or unpause contract
-    // ADMIN ONLY
-    pub fn set_pause_contract(&self) {
-        let admin = utils::get_key(KEY_NAME_ADMIN);
-
-        if runtime::get_caller() != admin {
-            revert(ApiError::InvalidPurse)
-        }
-
-        let current_state: bool = utils::get_key(KEY_NAME_PAUSED);
-
-        utils::set_key(KEY_NAME_PAUSED, !current_state);
-    }
-}
-
-// compares bytes from 16..48 (32) to see if it matches the caller
-fn caller_is_recipient(src: &[u8]) {
-    let caller_bytes: [u8; 32] = runtime::get_caller().as_bytes().try_into().unwrap();
-    let recipient = VestInfo::unpack_recipient(src);
-
-    if recipient != caller_bytes {
-        runtime::revert(ApiError::InvalidPurse)
-    }
-}
-
-// ensure id is less than current index
-fn is_id_valid(input: u64) {
-    let current_index: u64 = utils::get_key(KEY_NAME_INDEX);
-
-    if current_index < input {
-        revert(ApiError::InvalidArgument)
-    }
-}
-
-// ensure entry exists
-fn is_valid_entry(src: &[u8]) {
-    if src.is_empty() {
-        revert(ApiError::MissingKey)
-    }
-}
-
-// retrieve lock and validate caller and lock info
-fn get_lock(lock_id: u64) -> (VestInfo, URef, String) {
-    is_id_valid(lock_id);
-
-    let seed_uref = *runtime::get_key(KEY_NAME_DIC_LOCK_INFOS)
-        .unwrap_or_revert()
-        .as_uref()
-        .unwrap_or_revert();
-
-    let dictionary_item_key = make_lock_key_id(lock_id);
-
-    let lock_bytes = storage::dictionary_get::<Vec<u8>>(seed_uref, &dictionary_item_key)
-        .unwrap_or_revert_with(ApiError::MissingKey)
-        .unwrap_or_revert();
-
-    // check if info exist (hasn't been cleared)
-    is_valid_entry(&lock_bytes);
-
-    // check that the caller is the owner of the lock
-    caller_is_recipient(&lock_bytes);
-
-    let lock = VestInfo::unpack(lock_bytes);
-    (lock, seed_uref, dictionary_item_key)
-}
-
-fn update_storage(
-    seed_uref: URef,
-    dictionary_item_key: String,
-    res: Vec<u8>,
-    index: u64,
-    user_key: String,
-) {
-    storage::dictionary_put(seed_uref, &dictionary_item_key, res);
-    let exists = runtime::has_key(&user_key);
-    match exists {
-        false => {
-            let mut id_arr: Vec<u64> = Vec::new();
-            id_arr.push(index);
-            utils::set_key(&user_key, id_arr)
-        }
-        _ => {
-            let mut id_arr: Vec<u64> = utils::get_key(&user_key);
-            id_arr.push(index);
-            utils::set_key(&user_key, id_arr);
-        }
-    }
-}
-
-fn update_storage_transfer_lock(
-    seed_uref: URef,
-    dictionary_item_key: String,
-    index: u64,
-    old_owner_key: String,
-    new_owner_key: String,
-    new_lock: Vec<u8>,
-) {
-    // get ids for old user
-    let mut id_arr: Vec<u64> = utils::get_key(&old_owner_key);
-    // remove it
-    let idx = id_arr.iter().position(|x| *x == index).unwrap();
-    id_arr.swap_remove(idx);
-    // update storage
-    utils::set_key(&old_owner_key, id_arr);
-    // NEW USER
-    storage::dictionary_put(seed_uref, &dictionary_item_key, new_lock);
-    let exists = runtime::has_key(&new_owner_key);
-    match exists {
-        false => {
-            let mut id_arr: Vec<u64> = Vec::new();
-            id_arr.push(index);
-            utils::set_key(&new_owner_key, id_arr)
-        }
-        _ => {
-            let mut id_arr_2: Vec<u64> = utils::get_key(&new_owner_key);
-            // add it
-            id_arr_2.push(index);
-            // update storage
-            utils::set_key(&new_owner_key, id_arr_2);
-        }
-    }
-}
-
-fn is_paused() -> bool {
-    utils::get_key(KEY_NAME_PAUSED)
-}
-
-/*
-get contract hash
-*/
-fn self_contract_hash() -> ContractPackageHash {
-    utils::get_key(KEY_NAME_SELF_CONTRACT_HASH)
-}
-
-/*
-set contract hash - used in token transfer on claim() and lock( ).
-*/
-fn set_self_contract_hash(conthash: String) {
-    let self_acc_hash = ContractPackageHash::from_formatted_str(conthash.as_str())
-        .expect("self contract string format is error");
-    utils::set_key(KEY_NAME_SELF_CONTRACT_HASH, self_acc_hash);
-}
-
-/*
-for organizing data for locks by index
-*/
-
-fn make_lock_key_id(lock_id: u64) -> String {
-    let c_hash = self_contract_hash().to_string();
-    let (pre_key, _) = c_hash.split_at(15);
-    let append = lock_id.to_string();
-
-    String::from(pre_key) + &append
-}
+extern crate alloc;
+
+use core::convert::TryInto;
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use casper_contract::{
+    contract_api::{
+        runtime::{self, revert},
+        storage,
+    },
+    unwrap_or_revert::UnwrapOrRevert,
+};
+
+use casper_types::{
+    account::AccountHash, bytesrepr::ToBytes, ApiError, ContractHash, ContractPackageHash, Key,
+    URef, U256,
+};
+
+use crate::{constants::KEY_NAME_INDEX, interact_stake::interact_stake, utils, VestError};
+use crate::{constants::KEY_NAME_INITIALIZED, interact_token::interact_erc20};
+
+use crate::constants::{
+    DELEGATION_PRECISION, KEY_NAME_ADMIN, KEY_NAME_DIC_DELEGATIONS, KEY_NAME_DIC_DELEGATION_POOLS,
+    KEY_NAME_DIC_LINEAR_VESTS, KEY_NAME_DIC_LOCK_INFOS, KEY_NAME_PAUSED,
+    KEY_NAME_SELF_CONTRACT_HASH, KEY_NAME_STAKE_CONTRACT, KEY_NAME_STAKE_PACKAGE,
+};
+
+#[derive(Default)]
+pub struct VestContract;
+
+#[derive(Clone, Copy)]
+pub struct LockSchedule {
+    // 8
+    release: u64,
+    // 32
+    amount: U256,
+}
+
+pub struct VestInfo {
+    // 8 : 8
+    id: u64,
+    // 8 : 16
+    lock_time: u64,
+    // 32 : 48
+    recipient: AccountHash,
+    // 32 : 80
+    token_hash: ContractHash,
+    // 32 : 112
+    grantor: AccountHash,
+    // 1 : 113
+    revocable: bool,
+    // 40 : (113 + 40(num))
+    schedules: Vec<LockSchedule>,
+}
+
+impl VestInfo {
+    // returns all lock info as a byte array
+    fn pack(&self) -> Vec<u8> {
+        let id_bytes = self.id.into_bytes().unwrap_or_revert();
+        let lock_time_bytes = self.lock_time.into_bytes().unwrap_or_revert();
+        let recipient_bytes = self.recipient.as_bytes();
+        let token_hash_bytes = self.token_hash.value();
+        let grantor_bytes = self.grantor.as_bytes();
+
+        let mut res: Vec<u8> = Vec::new();
+
+        for i in id_bytes {
+            res.push(i);
+        }
+
+        for i in 8..16 {
+            res.push(lock_time_bytes[i - 8])
+        }
+
+        for i in 16..48 {
+            res.push(recipient_bytes[i - 16])
+        }
+
+        for i in 48..80 {
+            res.push(token_hash_bytes[i - 48])
+        }
+
+        for i in 80..112 {
+            res.push(grantor_bytes[i - 80])
+        }
+
+        res.push(self.revocable as u8);
+
+        let mut counter = 0;
+
+        while counter < self.schedules.len() {
+            let slice_to_pack = &self.schedules[counter];
+            let schedule_bytes = Self::pack_schedule(slice_to_pack);
+            for i in schedule_bytes {
+                res.push(i);
+            }
+            counter += 1
+        }
+
+        res
+    }
+
+    fn pack_schedule(src: &LockSchedule) -> [u8; 40] {
+        let mut res = [0u8; 40];
+
+        let mut little_endian_bytes = [0_u8; 64];
+
+        src.amount.to_little_endian(&mut little_endian_bytes[..32]);
+
+        let release = src.release.to_le_bytes();
+
+        res[..8].copy_from_slice(&release[..8]);
+
+        res[8..40].copy_from_slice(&little_endian_bytes[..32]);
+
+        res
+    }
+
+    fn unpack(src: Vec<u8>) -> Self {
+        let id = u64::from_le_bytes(src[0..8].try_into().unwrap());
+        let lock_time = u64::from_le_bytes(src[8..16].try_into().unwrap());
+        let recipient: AccountHash = src[16..48].try_into().unwrap();
+        let token_hash: ContractHash = src[48..80].try_into().unwrap();
+        let grantor: AccountHash = src[80..112].try_into().unwrap();
+        let revocable = src[112] != 0;
+
+        let (_, slice_schedules) = src.split_at(113);
+
+        let schedules = Self::unpack_all_schedules(slice_schedules.to_vec());
+
+        Self {
+            id,
+            lock_time,
+            recipient,
+            token_hash,
+            grantor,
+            revocable,
+            schedules,
+        }
+    }
+
+    fn unpack_schedule(src: &[u8]) -> LockSchedule {
+        let release = u64::from_le_bytes(src[0..8].try_into().unwrap());
+        let amount = U256::from_little_endian(&src[8..40]);
+
+        LockSchedule { release, amount }
+    }
+
+    fn unpack_all_schedules(src: Vec<u8>) -> Vec<LockSchedule> {
+        if src.len() % 40 != 0 {
+            revert(ApiError::Formatting)
+        }
+
+        let mut schedules: Vec<LockSchedule> = Vec::new();
+
+        let mut counter = 0;
+        let total_schedules = src.len().checked_div(40).unwrap_or_revert();
+
+        while counter < total_schedules {
+            let offset = counter.checked_mul(40).unwrap_or_revert();
+            let end_idx = offset.checked_add(40).unwrap_or_revert();
+            let s: LockSchedule = Self::unpack_schedule(&src[offset..end_idx]);
+            schedules.push(s);
+            counter += 1
+        }
+        schedules
+    }
+
+    // gets the recipient account from LockInfo
+    // used in caller_is_recipient() function
+    fn unpack_recipient(src: &[u8]) -> [u8; 32] {
+        let recipient: [u8; 32] = src[16..48].try_into().unwrap();
+        recipient
+    }
+
+    fn clear_entry() -> Vec<u8> {
+        let clear_entry = [0_u8; 0];
+        clear_entry.to_vec()
+    }
+
+    // tokens of the lock that haven't been claimed yet
+    fn unclaimed(&self) -> U256 {
+        let mut amount = U256::zero();
+
+        for s in self.schedules.iter() {
+            amount = amount
+                .checked_add(s.amount)
+                .unwrap_or_revert_with(VestError::Overflow);
+        }
+
+        amount
+    }
+}
+
+// tokens of a lock unlocking every second from start
+// to end, instead of in schedules. Nothing unlocks
+// before cliff
+pub struct LinearVest {
+    // 8 : 8
+    start: u64,
+    // 8 : 16
+    cliff: u64,
+    // 8 : 24
+    end: u64,
+    // 32 : 56
+    amount: U256,
+    // 32 : 88
+    claimed: U256,
+}
+
+impl LinearVest {
+    fn pack(&self) -> Vec<u8> {
+        let mut res: Vec<u8> = Vec::new();
+
+        for i in self.start.to_le_bytes() {
+            res.push(i)
+        }
+
+        for i in self.cliff.to_le_bytes() {
+            res.push(i)
+        }
+
+        for i in self.end.to_le_bytes() {
+            res.push(i)
+        }
+
+        for i in pack_u256(self.amount) {
+            res.push(i)
+        }
+
+        for i in pack_u256(self.claimed) {
+            res.push(i)
+        }
+
+        res
+    }
+
+    fn unpack(src: Vec<u8>) -> Self {
+        let start = u64::from_le_bytes(src[0..8].try_into().unwrap());
+        let cliff = u64::from_le_bytes(src[8..16].try_into().unwrap());
+        let end = u64::from_le_bytes(src[16..24].try_into().unwrap());
+        let amount = U256::from_little_endian(&src[24..56]);
+        let claimed = U256::from_little_endian(&src[56..88]);
+
+        Self {
+            start,
+            cliff,
+            end,
+            amount,
+            claimed,
+        }
+    }
+
+    // tokens unlocked by timestamp, claimed or not
+    fn vested(&self, timestamp: u64) -> U256 {
+        if timestamp < self.cliff {
+            return U256::zero();
+        }
+
+        if timestamp >= self.end {
+            return self.amount;
+        }
+
+        let elapsed = timestamp
+            .checked_sub(self.start)
+            .unwrap_or_revert_with(VestError::Underflow);
+        let duration = self
+            .end
+            .checked_sub(self.start)
+            .unwrap_or_revert_with(VestError::Underflow);
+
+        self.amount
+            .checked_mul(U256::from(elapsed))
+            .unwrap_or_revert_with(VestError::Overflow)
+            / U256::from(duration)
+    }
+
+    // marks the tokens unlocked by timestamp as claimed,
+    // returns the amount not claimed before
+    fn claim(&mut self, timestamp: u64) -> U256 {
+        let amount = self
+            .vested(timestamp)
+            .checked_sub(self.claimed)
+            .unwrap_or_revert_with(VestError::Underflow);
+
+        self.claimed = self
+            .claimed
+            .checked_add(amount)
+            .unwrap_or_revert_with(VestError::Overflow);
+
+        amount
+    }
+
+    // stops the stream at timestamp, returns the
+    // tokens that hadn't unlocked by then
+    fn revoke(&mut self, timestamp: u64) -> U256 {
+        let vested = self.vested(timestamp);
+        let unvested = self
+            .amount
+            .checked_sub(vested)
+            .unwrap_or_revert_with(VestError::Underflow);
+
+        // what is left is unlocked from now on
+        self.amount = vested;
+        self.end = self.end.min(timestamp);
+
+        unvested
+    }
+
+    fn unclaimed(&self) -> U256 {
+        self.amount
+            .checked_sub(self.claimed)
+            .unwrap_or_revert_with(VestError::Underflow)
+    }
+}
+
+// unclaimed tokens of a lock staked in a pool
+// of the staking contract
+pub struct Delegation {
+    // 8 : 8
+    pool_id: u64,
+    // 32 : 40
+    amount: U256,
+    // 32 : 72
+    reward_debt: U256,
+}
+
+impl Delegation {
+    fn pack(&self) -> Vec<u8> {
+        let mut res: Vec<u8> = Vec::new();
+
+        for i in self.pool_id.to_le_bytes() {
+            res.push(i)
+        }
+
+        for i in pack_u256(self.amount) {
+            res.push(i)
+        }
+
+        for i in pack_u256(self.reward_debt) {
+            res.push(i)
+        }
+
+        res
+    }
+
+    fn unpack(src: Vec<u8>) -> Self {
+        let pool_id = u64::from_le_bytes(src[0..8].try_into().unwrap());
+        let amount = U256::from_little_endian(&src[8..40]);
+        let reward_debt = U256::from_little_endian(&src[40..72]);
+
+        Self {
+            pool_id,
+            amount,
+            reward_debt,
+        }
+    }
+}
+
+// the vesting contract's stake in a pool, shared by
+// every lock delegated to it by delegated amount
+pub struct DelegationPool {
+    // 32 : 32
+    reward_token: ContractHash,
+    // 32 : 64
+    total_delegated: U256,
+    // 32 : 96
+    acc_reward_per_share: U256,
+    // 1  : 97
+    exited: bool,
+}
+
+impl DelegationPool {
+    fn pack(&self) -> Vec<u8> {
+        let mut res: Vec<u8> = Vec::new();
+
+        for i in self.reward_token.value() {
+            res.push(i)
+        }
+
+        for i in pack_u256(self.total_delegated) {
+            res.push(i)
+        }
+
+        for i in pack_u256(self.acc_reward_per_share) {
+            res.push(i)
+        }
+
+        res.push(self.exited as u8);
+
+        res
+    }
+
+    fn unpack(src: Vec<u8>) -> Self {
+        let reward_token: ContractHash = src[0..32].try_into().unwrap();
+        let total_delegated = U256::from_little_endian(&src[32..64]);
+        let acc_reward_per_share = U256::from_little_endian(&src[64..96]);
+        let exited = src[96] != 0;
+
+        Self {
+            reward_token,
+            total_delegated,
+            acc_reward_per_share,
+            exited,
+        }
+    }
+
+    // adds rewards the pool paid to the vesting contract
+    fn accrue(&mut self, rewards: U256) {
+        if rewards.is_zero() || self.total_delegated.is_zero() {
+            return;
+        }
+
+        let increment = rewards
+            .checked_mul(U256::from(DELEGATION_PRECISION))
+            .and_then(|value| value.checked_div(self.total_delegated))
+            .unwrap_or_revert_with(VestError::Overflow);
+
+        self.acc_reward_per_share = self
+            .acc_reward_per_share
+            .checked_add(increment)
+            .unwrap_or_revert_with(VestError::Overflow);
+    }
+
+    // rewards earned by amount of delegated tokens so far
+    fn accrued(&self, amount: U256) -> U256 {
+        amount
+            .checked_mul(self.acc_reward_per_share)
+            .unwrap_or_revert_with(VestError::Overflow)
+            / U256::from(DELEGATION_PRECISION)
+    }
+
+    fn pending(&self, delegation: &Delegation) -> U256 {
+        self.accrued(delegation.amount)
+            .checked_sub(delegation.reward_debt)
+            .unwrap_or_revert_with(VestError::Underflow)
+    }
+}
+
+impl VestContract {
+    // init to create dictionary for lock info
+    pub fn init(&self, conthash: String) {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        let is_initialized: bool = utils::get_key(KEY_NAME_INITIALIZED);
+
+        if is_initialized {
+            revert(ApiError::PermissionDenied)
+        }
+
+        storage::new_dictionary(KEY_NAME_DIC_LOCK_INFOS).unwrap_or_revert();
+        storage::new_dictionary(KEY_NAME_DIC_DELEGATIONS).unwrap_or_revert();
+        storage::new_dictionary(KEY_NAME_DIC_DELEGATION_POOLS).unwrap_or_revert();
+        storage::new_dictionary(KEY_NAME_DIC_LINEAR_VESTS).unwrap_or_revert();
+
+        set_self_contract_hash(conthash);
+
+        utils::set_key(KEY_NAME_INDEX, 0u64);
+        utils::set_key(KEY_NAME_INITIALIZED, true);
+    }
+
+    // @tokh - token to lock
+    // @cliff_amount - amount to lock
+    // @cliff_durtime - time from now until first unlock
+    // @time_between_locks - seconds between each unlock
+    // @number_of_locks - number of locks
+    pub fn lock(
+        &self,
+        tokh: ContractHash,
+        cliff_amount: U256,
+        cliff_durtime: u64,
+        time_between_locks: u64,
+        number_of_locks: u32,
+    ) {
+        self.lock_for(
+            tokh,
+            runtime::get_caller(),
+            cliff_amount,
+            cliff_durtime,
+            time_between_locks,
+            number_of_locks,
+            false,
+        )
+    }
+
+    // locks the caller's tokens for recipient, who
+    // owns the lock and claims the unlocked tokens
+    // @recipient - owner of the new lock
+    // @revocable - whether the caller can revoke the lock
+    // other args as in lock()
+    #[allow(clippy::too_many_arguments)]
+    pub fn lock_for(
+        &self,
+        tokh: ContractHash,
+        recipient: AccountHash,
+        cliff_amount: U256,
+        cliff_durtime: u64,
+        time_between_locks: u64,
+        number_of_locks: u32,
+        revocable: bool,
+    ) {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        if is_paused() {
+            revert(VestError::Paused)
+        }
+
+        let mut schedules: Vec<LockSchedule> = Vec::new();
+
+        let timestamp: u64 = runtime::get_blocktime().into();
+
+        let first_unlock: u64 = timestamp
+            .checked_add(cliff_durtime)
+            .unwrap_or_revert_with(VestError::Overflow);
+
+        if number_of_locks == 0 {
+            revert(ApiError::InvalidArgument)
+        }
+
+        while schedules.len() < number_of_locks as usize {
+            let release = time_between_locks
+                .checked_mul(schedules.len() as u64)
+                .and_then(|offset| first_unlock.checked_add(offset))
+                .unwrap_or_revert_with(VestError::Overflow);
+            let amount = cliff_amount
+                .checked_div(U256::from(number_of_locks))
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let s = LockSchedule { release, amount };
+            schedules.push(s)
+        }
+
+        create_lock(tokh, recipient, cliff_amount, revocable, schedules);
+    }
+
+    // locks the caller's tokens for recipient with uneven tranches
+    // @recipient - owner of the new lock
+    // @cliff_amount - amount to lock
+    // @lock_schedules - (release, amount) pairs with ascending
+    //                   releases, amounts summing to cliff_amount
+    // @revocable - whether the caller can revoke the lock
+    pub fn lock_custom(
+        &self,
+        tokh: ContractHash,
+        recipient: AccountHash,
+        cliff_amount: U256,
+        lock_schedules: Vec<(u64, U256)>,
+        revocable: bool,
+    ) {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        if is_paused() {
+            revert(VestError::Paused)
+        }
+
+        if lock_schedules.is_empty() {
+            revert(ApiError::InvalidArgument)
+        }
+
+        let mut schedules: Vec<LockSchedule> = Vec::new();
+        let mut total = U256::zero();
+
+        for (release, amount) in lock_schedules {
+            if amount.is_zero() {
+                revert(ApiError::InvalidArgument)
+            }
+
+            if let Some(last) = schedules.last() {
+                if release <= last.release {
+                    revert(ApiError::InvalidArgument)
+                }
+            }
+
+            total = total
+                .checked_add(amount)
+                .unwrap_or_revert_with(VestError::Overflow);
+
+            schedules.push(LockSchedule { release, amount })
+        }
+
+        // every transferred token has to be in a tranche
+        if total != cliff_amount {
+            revert(ApiError::InvalidArgument)
+        }
+
+        create_lock(tokh, recipient, cliff_amount, revocable, schedules);
+    }
+
+    // locks the caller's tokens for recipient, unlocking
+    // them every second from start_time to end_time
+    // @recipient - owner of the new lock
+    // @cliff_amount - amount to lock
+    // @start_time - when the tokens start to unlock, can be in the past
+    // @cliff_duration - seconds after start_time before the
+    //                   first claim, 0 for no cliff
+    // @end_time - when every token is unlocked
+    // @revocable - whether the caller can revoke the lock
+    #[allow(clippy::too_many_arguments)]
+    pub fn lock_linear(
+        &self,
+        tokh: ContractHash,
+        recipient: AccountHash,
+        cliff_amount: U256,
+        start_time: u64,
+        cliff_duration: u64,
+        end_time: u64,
+        revocable: bool,
+    ) {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        if is_paused() {
+            revert(VestError::Paused)
+        }
+
+        let cliff = start_time
+            .checked_add(cliff_duration)
+            .unwrap_or_revert_with(VestError::Overflow);
+
+        if cliff_amount.is_zero() || end_time <= start_time || cliff > end_time {
+            revert(ApiError::InvalidArgument)
+        }
+
+        let lock_id: u64 = utils::get_key(KEY_NAME_INDEX);
+
+        // the VestInfo holds no schedules, the
+        // tokens are tracked by the LinearVest
+        create_lock(tokh, recipient, cliff_amount, revocable, Vec::new());
+
+        let vest = LinearVest {
+            start: start_time,
+            cliff,
+            end: end_time,
+            amount: cliff_amount,
+            claimed: U256::zero(),
+        };
+
+        save_linear_vest(lock_id, &vest);
+    }
+
+    // @lock_id = the lock to change
+    // @index = index of the lock to update
+    // @new_release = new release time
+    pub fn extend_lock(&mut self, lock_id: u64, index: u32, new_release: u64) {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        if is_paused() {
+            revert(VestError::Paused)
+        }
+        let (mut lock, seed_uref, dictionary_item_key) = get_lock(lock_id);
+
+        // can't update non-existant schedule
+        if index as usize >= lock.schedules.len() {
+            revert(ApiError::InvalidArgument)
+        }
+
+        if lock.schedules[index as usize].release > new_release {
+            revert(ApiError::None)
+        }
+
+        lock.schedules[index as usize].release = new_release;
+
+        // pack updated lock information into u8 array
+        let res = VestInfo::pack(&lock);
+
+        // update key
+        storage::dictionary_put(seed_uref, &dictionary_item_key, res)
+    }
+
+    // @lock_id = the lock to change
+    // @new_owner = who to transfer to
+    pub fn transfer_lock(&mut self, lock_id: u64, new_owner: AccountHash) {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        if is_paused() {
+            revert(VestError::Paused)
+        }
+        let (mut lock, seed_uref, dictionary_item_key) = get_lock(lock_id);
+
+        lock.recipient = new_owner;
+
+        // pack lock information into u8 array
+        let res = VestInfo::pack(&lock);
+
+        update_storage_transfer_lock(
+            seed_uref,
+            dictionary_item_key,
+            lock.id,
+            runtime::get_caller().to_string(),
+            new_owner.to_string(),
+            res,
+        )
+    }
+
+    // unlock tokens
+    // @lock_id - the lock to change
+    pub fn claim(&self, lock_id: u64) {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        if is_paused() {
+            revert(VestError::Paused)
+        }
+        let (mut lock, seed_uref, dictionary_item_key) = get_lock(lock_id);
+
+        let timestamp: u64 = runtime::get_blocktime().into();
+
+        let mut linear_vest = get_linear_vest(lock_id);
+
+        let mut amount_to_transfer = U256::zero();
+
+        match linear_vest.as_mut() {
+            Some(vest) => amount_to_transfer = vest.claim(timestamp),
+            None => {
+                for s in lock.schedules.iter_mut() {
+                    if timestamp >= s.release {
+                        amount_to_transfer = amount_to_transfer
+                            .checked_add(s.amount)
+                            .unwrap_or_revert_with(VestError::Overflow);
+                        s.amount = U256::zero()
+                    }
+                }
+            }
+        }
+
+        if amount_to_transfer.is_zero() {
+            revert(VestError::NothingToWithdraw)
+        }
+
+        // delegated tokens have to come back from the staking pool first
+        if let Some(delegation) = get_delegation(lock_id) {
+            unstake(lock_id, lock.recipient, delegation, amount_to_transfer);
+        }
+
+        // transfer tokens from this contract
+        let tx = interact_erc20::default().transfer(
+            lock.token_hash,
+            runtime::get_caller(),
+            amount_to_transfer,
+        );
+
+        if Some(tx).is_none() {
+            revert(ApiError::None)
+        }
+
+        let mut empty_locks: usize = 0;
+        let total_locks = lock.schedules.len();
+
+        for s in lock.schedules.iter() {
+            if s.amount.is_zero() {
+                empty_locks += 1
+            }
+        }
+
+        let all_claimed: bool = match &linear_vest {
+            Some(vest) => {
+                save_linear_vest(lock_id, vest);
+                vest.unclaimed().is_zero()
+            }
+            None => total_locks == empty_locks,
+        };
+
+        match all_claimed {
+            false => {
+                // pack lock information into u8 array
+                let res = VestInfo::pack(&lock);
+                // update key
+                storage::dictionary_put(seed_uref, &dictionary_item_key, res)
+            }
+            _ => {
+                // clear the entry if it is finished
+                storage::dictionary_put(seed_uref, &dictionary_item_key, VestInfo::clear_entry());
+                let mut id_arr: Vec<u64> =
+                    utils::get_key(runtime::get_caller().to_string().as_str());
+                let idx = id_arr.iter().position(|x| *x == lock_id).unwrap();
+                id_arr.swap_remove(idx);
+                utils::set_key(runtime::get_caller().to_string().as_str(), id_arr);
+            }
+        }
+    }
+
+    // returns the tokens of a revocable lock that haven't unlocked
+    // yet to the grantor, unlocked ones stay claimable by the
+    // recipient. The lock can't be revoked again
+    // GRANTOR ONLY
+    // @lock_id - the lock to revoke
+    pub fn revoke(&self, lock_id: u64) {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        if is_paused() {
+            revert(VestError::Paused)
+        }
+        let (lock_bytes, seed_uref, dictionary_item_key) = get_lock_bytes(lock_id);
+        let mut lock = VestInfo::unpack(lock_bytes);
+
+        if runtime::get_caller() != lock.grantor {
+            revert(ApiError::InvalidPurse)
+        }
+
+        if !lock.revocable {
+            revert(ApiError::InvalidArgument)
+        }
+
+        let timestamp: u64 = runtime::get_blocktime().into();
+
+        let mut linear_vest = get_linear_vest(lock_id);
+
+        let mut amount_to_return = U256::zero();
+
+        match linear_vest.as_mut() {
+            Some(vest) => amount_to_return = vest.revoke(timestamp),
+            None => {
+                for s in lock.schedules.iter_mut() {
+                    if timestamp < s.release {
+                        amount_to_return = amount_to_return
+                            .checked_add(s.amount)
+                            .unwrap_or_revert_with(VestError::Overflow);
+                        s.amount = U256::zero()
+                    }
+                }
+            }
+        }
+
+        if amount_to_return.is_zero() {
+            revert(VestError::NothingToWithdraw)
+        }
+
+        interact_erc20::default().transfer(lock.token_hash, lock.grantor, amount_to_return);
+
+        lock.revocable = false;
+
+        let unclaimed = match &linear_vest {
+            Some(vest) => {
+                save_linear_vest(lock_id, vest);
+                vest.unclaimed()
+            }
+            None => lock.unclaimed(),
+        };
+
+        if unclaimed.is_zero() {
+            // clear the entry, the recipient has nothing left to claim
+            storage::dictionary_put(seed_uref, &dictionary_item_key, VestInfo::clear_entry());
+            let recipient_key = lock.recipient.to_string();
+            let mut id_arr: Vec<u64> = utils::get_key(&recipient_key);
+            let idx = id_arr.iter().position(|x| *x == lock_id).unwrap();
+            id_arr.swap_remove(idx);
+            utils::set_key(&recipient_key, id_arr);
+        } else {
+            storage::dictionary_put(seed_uref, &dictionary_item_key, VestInfo::pack(&lock))
+        }
+    }
+
+    // sets the staking contract locks can be delegated to,
+    // it can't be changed once set
+    // ADMIN ONLY
+    pub fn set_stake_contract(
+        &self,
+        stake_contract: ContractHash,
+        stake_package: ContractPackageHash,
+    ) {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        let admin = utils::get_key(KEY_NAME_ADMIN);
+
+        if runtime::get_caller() != admin {
+            revert(ApiError::InvalidPurse)
+        }
+
+        if runtime::has_key(KEY_NAME_STAKE_CONTRACT) {
+            revert(ApiError::PermissionDenied)
+        }
+
+        utils::set_key(KEY_NAME_STAKE_CONTRACT, stake_contract);
+        utils::set_key(KEY_NAME_STAKE_PACKAGE, stake_package);
+    }
+
+    // stakes the unclaimed tokens of a lock in a pool of the
+    // staking contract, they stay locked under its schedules
+    // and rewards are paid to the lock recipient
+    // @lock_id - the lock to delegate
+    // @pool_id - pool of the staking contract
+    pub fn delegate_lock(&self, lock_id: u64, pool_id: u64) {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        if is_paused() {
+            revert(VestError::Paused)
+        }
+        let (lock, _, _) = get_lock(lock_id);
+
+        // revoke has to take the tokens back without going
+        // through a pool the recipient picked
+        if lock.revocable || get_delegation(lock_id).is_some() {
+            revert(ApiError::InvalidArgument)
+        }
+
+        let amount = match get_linear_vest(lock_id) {
+            Some(vest) => vest.unclaimed(),
+            None => lock.unclaimed(),
+        };
+
+        if amount.is_zero() {
+            revert(VestError::NothingToWithdraw)
+        }
+
+        let (keys, values) = interact_stake::default().get_pool(stake_contract(), pool_id);
+
+        let staking_token = *keys.get("staking_token").unwrap_or_revert();
+        let reward_token = *keys.get("reward_token").unwrap_or_revert();
+        let native_reward = *values.get("native_reward").unwrap_or_revert();
+        let cooldown = *values.get("cooldown").unwrap_or_revert();
+
+        // claims have to take tokens out of the pool right away,
+        // and CSPR rewards can't be paid to a contract
+        if staking_token != Key::Hash(lock.token_hash.value())
+            || !native_reward.is_zero()
+            || !cooldown.is_zero()
+        {
+            revert(ApiError::InvalidArgument)
+        }
+
+        let mut pool = get_delegation_pool(pool_id).unwrap_or(DelegationPool {
+            reward_token: ContractHash::new(reward_token.into_hash().unwrap_or_revert()),
+            total_delegated: U256::zero(),
+            acc_reward_per_share: U256::zero(),
+            exited: false,
+        });
+
+        // delegated locks still wait to leave an exited pool
+        if pool.exited {
+            revert(ApiError::InvalidArgument)
+        }
+
+        // settle the rewards earned so far before the stake grows
+        harvest_pool(pool_id, &mut pool);
+
+        interact_erc20::default().approve(lock.token_hash, stake_package(), amount);
+
+        let staked = interact_stake::default().deposit(stake_contract(), pool_id, amount);
+
+        // every delegated token has to be withdrawable
+        if staked != amount {
+            revert(ApiError::InvalidArgument)
+        }
+
+        pool.total_delegated = pool
+            .total_delegated
+            .checked_add(amount)
+            .unwrap_or_revert_with(VestError::Overflow);
+
+        let delegation = Delegation {
+            pool_id,
+            amount,
+            reward_debt: pool.accrued(amount),
+        };
+
+        save_delegation_pool(pool_id, &pool);
+        save_delegation(lock_id, &delegation);
+    }
+
+    // takes the delegated tokens of a lock back
+    // from its pool and pays the rewards earned
+    // @lock_id - the lock to undelegate
+    pub fn undelegate_lock(&self, lock_id: u64) {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        if is_paused() {
+            revert(VestError::Paused)
+        }
+        let (lock, _, _) = get_lock(lock_id);
+
+        let delegation = get_delegation(lock_id).unwrap_or_revert_with(ApiError::InvalidArgument);
+        let amount = delegation.amount;
+
+        unstake(lock_id, lock.recipient, delegation, amount);
+    }
+
+    // pays the rewards a delegated lock earned so far
+    // @lock_id - the delegated lock
+    pub fn harvest_lock(&self, lock_id: u64) {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        if is_paused() {
+            revert(VestError::Paused)
+        }
+        let (lock, _, _) = get_lock(lock_id);
+
+        let mut delegation =
+            get_delegation(lock_id).unwrap_or_revert_with(ApiError::InvalidArgument);
+        let mut pool = get_delegation_pool(delegation.pool_id).unwrap_or_revert();
+
+        harvest_pool(delegation.pool_id, &mut pool);
+
+        let pending = pool.pending(&delegation);
+        delegation.reward_debt = pool.accrued(delegation.amount);
+
+        save_delegation_pool(delegation.pool_id, &pool);
+        save_delegation(lock_id, &delegation);
+
+        pay_reward(&pool, lock.recipient, pending);
+    }
+
+    // takes every delegated token out of a pool in emergency mode,
+    // the locks keep their delegations until they are claimed or
+    // undelegated, without earning further rewards.
+    // Callable by anyone
+    // @pool_id - pool of the staking contract
+    pub fn exit_pool(&self, pool_id: u64) {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        let mut pool =
+            get_delegation_pool(pool_id).unwrap_or_revert_with(ApiError::InvalidArgument);
+
+        if pool.exited || pool.total_delegated.is_zero() {
+            revert(ApiError::InvalidArgument)
+        }
+
+        // reverts unless the pool is in emergency mode
+        interact_stake::default().emergency_withdraw(stake_contract(), pool_id);
+
+        pool.exited = true;
+
+        save_delegation_pool(pool_id, &pool);
+    }
+
+    // sets new admin
+    // @account - account hash of the new admin
+    // ADMIN ONLY
+    pub fn set_admin(&self, account: AccountHash) {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        let admin = utils::get_key(KEY_NAME_ADMIN);
+
+        if runtime::get_caller() != admin {
+            revert(ApiError::InvalidPurse)
+        }
+
+        utils::set_key(KEY_NAME_ADMIN, account)
+    }
+
+    // pause or unpause contract
+    // ADMIN ONLY
+    pub fn set_pause_contract(&self) {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        let admin = utils::get_key(KEY_NAME_ADMIN);
+
+        if runtime::get_caller() != admin {
+            revert(ApiError::InvalidPurse)
+        }
+
+        let current_state: bool = utils::get_key(KEY_NAME_PAUSED);
+
+        utils::set_key(KEY_NAME_PAUSED, !current_state);
+    }
+}
+
+// stores a new lock of the caller's tokens for recipient
+// and transfers cliff_amount of them to this contract.
+// The caller is the grantor of the lock
+fn create_lock(
+    tokh: ContractHash,
+    recipient: AccountHash,
+    cliff_amount: U256,
+    revocable: bool,
+    schedules: Vec<LockSchedule>,
+) {
+    let seed_uref = *runtime::get_key(KEY_NAME_DIC_LOCK_INFOS)
+        .unwrap_or_revert()
+        .as_uref()
+        .unwrap_or_revert();
+
+    let timestamp: u64 = runtime::get_blocktime().into();
+
+    // lock_id
+    let current_index: u64 = utils::get_key(KEY_NAME_INDEX);
+
+    let dictionary_item_key = make_lock_key_id(current_index);
+
+    let info: VestInfo = VestInfo {
+        id: current_index,
+        lock_time: timestamp,
+        recipient,
+        token_hash: tokh,
+        grantor: runtime::get_caller(),
+        revocable,
+        schedules,
+    };
+
+    // pack lock information into u8 array
+    let res = VestInfo::pack(&info);
+
+    // transfer tokens to this contract
+    let tx = interact_erc20::default().transfer_from(
+        tokh,
+        runtime::get_caller(),
+        self_contract_hash(),
+        cliff_amount,
+    );
+
+    if Some(tx).is_none() {
+        revert(ApiError::None)
+    }
+
+    // This identifies an item within the dictionary
+    // and either creates or updates the associated value.
+    update_storage(
+        seed_uref,
+        dictionary_item_key,
+        res,
+        current_index,
+        recipient.to_string(),
+    );
+
+    // update the global counter for lock_id
+    utils::set_key(
+        KEY_NAME_INDEX,
+        current_index
+            .checked_add(1)
+            .unwrap_or_revert_with(VestError::Overflow),
+    );
+}
+
+// compares bytes from 16..48 (32) to see if it matches the caller
+fn caller_is_recipient(src: &[u8]) {
+    let caller_bytes: [u8; 32] = runtime::get_caller().as_bytes().try_into().unwrap();
+    let recipient = VestInfo::unpack_recipient(src);
+
+    if recipient != caller_bytes {
+        runtime::revert(ApiError::InvalidPurse)
+    }
+}
+
+// ensure id is less than current index
+fn is_id_valid(input: u64) {
+    let current_index: u64 = utils::get_key(KEY_NAME_INDEX);
+
+    if current_index < input {
+        revert(ApiError::InvalidArgument)
+    }
+}
+
+// ensure entry exists
+fn is_valid_entry(src: &[u8]) {
+    if src.is_empty() {
+        revert(ApiError::MissingKey)
+    }
+}
+
+// retrieve lock and validate caller and lock info
+fn get_lock(lock_id: u64) -> (VestInfo, URef, String) {
+    let (lock_bytes, seed_uref, dictionary_item_key) = get_lock_bytes(lock_id);
+
+    // check that the caller is the owner of the lock
+    caller_is_recipient(&lock_bytes);
+
+    let lock = VestInfo::unpack(lock_bytes);
+    (lock, seed_uref, dictionary_item_key)
+}
+
+// retrieve lock info without checking the caller
+fn get_lock_bytes(lock_id: u64) -> (Vec<u8>, URef, String) {
+    is_id_valid(lock_id);
+
+    let seed_uref = *runtime::get_key(KEY_NAME_DIC_LOCK_INFOS)
+        .unwrap_or_revert()
+        .as_uref()
+        .unwrap_or_revert();
+
+    let dictionary_item_key = make_lock_key_id(lock_id);
+
+    let lock_bytes = storage::dictionary_get::<Vec<u8>>(seed_uref, &dictionary_item_key)
+        .unwrap_or_revert_with(ApiError::MissingKey)
+        .unwrap_or_revert();
+
+    // check if info exist (hasn't been cleared)
+    is_valid_entry(&lock_bytes);
+
+    (lock_bytes, seed_uref, dictionary_item_key)
+}
+
+fn update_storage(
+    seed_uref: URef,
+    dictionary_item_key: String,
+    res: Vec<u8>,
+    index: u64,
+    user_key: String,
+) {
+    storage::dictionary_put(seed_uref, &dictionary_item_key, res);
+    let exists = runtime::has_key(&user_key);
+    match exists {
+        false => {
+            let mut id_arr: Vec<u64> = Vec::new();
+            id_arr.push(index);
+            utils::set_key(&user_key, id_arr)
+        }
+        _ => {
+            let mut id_arr: Vec<u64> = utils::get_key(&user_key);
+            id_arr.push(index);
+            utils::set_key(&user_key, id_arr);
+        }
+    }
+}
+
+fn update_storage_transfer_lock(
+    seed_uref: URef,
+    dictionary_item_key: String,
+    index: u64,
+    old_owner_key: String,
+    new_owner_key: String,
+    new_lock: Vec<u8>,
+) {
+    // get ids for old user
+    let mut id_arr: Vec<u64> = utils::get_key(&old_owner_key);
+    // remove it
+    let idx = id_arr.iter().position(|x| *x == index).unwrap();
+    id_arr.swap_remove(idx);
+    // update storage
+    utils::set_key(&old_owner_key, id_arr);
+    // NEW USER
+    storage::dictionary_put(seed_uref, &dictionary_item_key, new_lock);
+    let exists = runtime::has_key(&new_owner_key);
+    match exists {
+        false => {
+            let mut id_arr: Vec<u64> = Vec::new();
+            id_arr.push(index);
+            utils::set_key(&new_owner_key, id_arr)
+        }
+        _ => {
+            let mut id_arr_2: Vec<u64> = utils::get_key(&new_owner_key);
+            // add it
+            id_arr_2.push(index);
+            // update storage
+            utils::set_key(&new_owner_key, id_arr_2);
+        }
+    }
+}
+
+// withdraws amount of a lock's delegation from its pool
+// and pays the lock's rewards to the recipient
+fn unstake(lock_id: u64, recipient: AccountHash, mut delegation: Delegation, amount: U256) {
+    let amount = amount.min(delegation.amount);
+    let mut pool = get_delegation_pool(delegation.pool_id).unwrap_or_revert();
+
+    exit_emergency_pool(delegation.pool_id, &mut pool);
+
+    // tokens of an exited pool are back in the vesting contract
+    if !pool.exited {
+        let (_, rewards) =
+            interact_stake::default().withdraw(stake_contract(), delegation.pool_id, amount);
+
+        pool.accrue(rewards);
+    }
+
+    let pending = pool.pending(&delegation);
+
+    delegation.amount = delegation
+        .amount
+        .checked_sub(amount)
+        .unwrap_or_revert_with(VestError::Underflow);
+    pool.total_delegated = pool
+        .total_delegated
+        .checked_sub(amount)
+        .unwrap_or_revert_with(VestError::Underflow);
+    delegation.reward_debt = pool.accrued(delegation.amount);
+
+    // the pool can take delegations again once every lock has left
+    if pool.total_delegated.is_zero() {
+        pool.exited = false;
+    }
+
+    save_delegation_pool(delegation.pool_id, &pool);
+    save_delegation(lock_id, &delegation);
+
+    pay_reward(&pool, recipient, pending);
+}
+
+// claims the rewards of the vesting contract's stake in a pool
+fn harvest_pool(pool_id: u64, pool: &mut DelegationPool) {
+    exit_emergency_pool(pool_id, pool);
+
+    // the staking contract has no position to harvest
+    if pool.total_delegated.is_zero() || pool.exited {
+        return;
+    }
+
+    let rewards = interact_stake::default().harvest(stake_contract(), pool_id);
+    pool.accrue(rewards);
+}
+
+// a pool in emergency mode closes withdrawals and harvests, so the
+// vesting contract takes every delegated token out of it with
+// emergency_withdraw before it touches the pool. Its exit terms can't
+// change otherwise: the staking contract freezes the cooldown once
+// anything is staked, and the staking token is fixed
+fn exit_emergency_pool(pool_id: u64, pool: &mut DelegationPool) {
+    if pool.exited || pool.total_delegated.is_zero() {
+        return;
+    }
+
+    let (_, values) = interact_stake::default().get_pool(stake_contract(), pool_id);
+
+    if values.get("emergency").unwrap_or_revert().is_zero() {
+        return;
+    }
+
+    interact_stake::default().emergency_withdraw(stake_contract(), pool_id);
+
+    pool.exited = true;
+}
+
+fn pay_reward(pool: &DelegationPool, recipient: AccountHash, amount: U256) {
+    if !amount.is_zero() {
+        interact_erc20::default().transfer(pool.reward_token, recipient, amount);
+    }
+}
+
+fn get_delegation(lock_id: u64) -> Option<Delegation> {
+    let seed_uref = *runtime::get_key(KEY_NAME_DIC_DELEGATIONS)
+        .unwrap_or_revert()
+        .as_uref()
+        .unwrap_or_revert();
+
+    storage::dictionary_get::<Vec<u8>>(seed_uref, &make_lock_key_id(lock_id))
+        .unwrap_or_revert()
+        .filter(|bytes| !bytes.is_empty())
+        .map(Delegation::unpack)
+}
+
+// clears the entry once nothing is delegated
+fn save_delegation(lock_id: u64, delegation: &Delegation) {
+    let seed_uref = *runtime::get_key(KEY_NAME_DIC_DELEGATIONS)
+        .unwrap_or_revert()
+        .as_uref()
+        .unwrap_or_revert();
+
+    let res = if delegation.amount.is_zero() {
+        VestInfo::clear_entry()
+    } else {
+        delegation.pack()
+    };
+
+    storage::dictionary_put(seed_uref, &make_lock_key_id(lock_id), res)
+}
+
+fn get_linear_vest(lock_id: u64) -> Option<LinearVest> {
+    let seed_uref = *runtime::get_key(KEY_NAME_DIC_LINEAR_VESTS)
+        .unwrap_or_revert()
+        .as_uref()
+        .unwrap_or_revert();
+
+    storage::dictionary_get::<Vec<u8>>(seed_uref, &make_lock_key_id(lock_id))
+        .unwrap_or_revert()
+        .map(LinearVest::unpack)
+}
+
+fn save_linear_vest(lock_id: u64, vest: &LinearVest) {
+    let seed_uref = *runtime::get_key(KEY_NAME_DIC_LINEAR_VESTS)
+        .unwrap_or_revert()
+        .as_uref()
+        .unwrap_or_revert();
+
+    storage::dictionary_put(seed_uref, &make_lock_key_id(lock_id), vest.pack())
+}
+
+fn get_delegation_pool(pool_id: u64) -> Option<DelegationPool> {
+    let seed_uref = *runtime::get_key(KEY_NAME_DIC_DELEGATION_POOLS)
+        .unwrap_or_revert()
+        .as_uref()
+        .unwrap_or_revert();
+
+    storage::dictionary_get::<Vec<u8>>(seed_uref, &pool_id.to_string())
+        .unwrap_or_revert()
+        .map(DelegationPool::unpack)
+}
+
+fn save_delegation_pool(pool_id: u64, pool: &DelegationPool) {
+    let seed_uref = *runtime::get_key(KEY_NAME_DIC_DELEGATION_POOLS)
+        .unwrap_or_revert()
+        .as_uref()
+        .unwrap_or_revert();
+
+    storage::dictionary_put(seed_uref, &pool_id.to_string(), pool.pack())
+}
+
+fn pack_u256(value: U256) -> [u8; 32] {
+    let mut bytes = [0_u8; 32];
+    value.to_little_endian(&mut bytes);
+    bytes
+}
+
+fn stake_contract() -> ContractHash {
+    utils::get_key(KEY_NAME_STAKE_CONTRACT)
+}
+
+fn stake_package() -> ContractPackageHash {
+    utils::get_key(KEY_NAME_STAKE_PACKAGE)
+}
+
+fn is_paused() -> bool {
+    utils::get_key(KEY_NAME_PAUSED)
+}
+
+/*
+get contract hash
+*/
+fn self_contract_hash() -> ContractPackageHash {
+    utils::get_key(KEY_NAME_SELF_CONTRACT_HASH)
+}
+
+/*
+set contract hash - used in token transfer on claim() and lock( ).
+*/
+fn set_self_contract_hash(conthash: String) {
+    let self_acc_hash = ContractPackageHash::from_formatted_str(conthash.as_str())
+        .expect("self contract string format is error");
+    utils::set_key(KEY_NAME_SELF_CONTRACT_HASH, self_acc_hash);
+}
+
+/*
+for organizing data for locks by index
+*/
+
+fn make_lock_key_id(lock_id: u64) -> String {
+    let c_hash = self_contract_hash().to_string();
+    let (pre_key, _) = c_hash.split_at(15);
+    let append = lock_id.to_string();
+
+    String::from(pre_key) + &append
+}
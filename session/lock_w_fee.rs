@@ -23,6 +23,8 @@ pub extern "C" fn call() {
     let time_between_locks: u64 = runtime::get_named_arg("time_between_locks");
     let num_locks: u32 = runtime::get_named_arg("num_locks");
 
+    // recipient of the CSPR fee, the lock itself belongs to the caller.
+    // The lock_for entry point locks tokens for another account
     let recipient: String = runtime::get_named_arg("recipient");
     let recip: AccountHash = AccountHash::from_formatted_str(&recipient).unwrap();
     let amount: U512 = runtime::get_named_arg("amount");
@@ -0,0 +1,143 @@
+#![no_std]
+#![no_main]
+
+// #[cfg(not(target_arch = "wasm32"))]
+// compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+
+use casper_contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{runtime_args, ContractHash, ContractPackageHash, Key, RuntimeArgs, U256, U512};
+
+// entry_point 0: approve the staking contract for amount of the
+//                staking token and deposit it, in one deploy
+// entry_point 1: harvest rewards of pool_ids
+// entry_point 2: withdraw amount from pool_id and claim its rewards
+// entry_point 3: approve the factory for total_reward of the reward
+//                token and install a funded pool contract through it
+// entry_point 4: move total_reward motes from the main purse into a
+//                temporary purse and add a native pool funded from it
+#[no_mangle]
+pub extern "C" fn call() {
+    let entry_point: u8 = runtime::get_named_arg("entry_point");
+    let contract_hash: String = runtime::get_named_arg("contract_hash");
+
+    let contract = ContractHash::from_formatted_str(&contract_hash).unwrap();
+
+    match entry_point {
+        0 => {
+            let token_hash: String = runtime::get_named_arg("token_hash");
+            let stake_package: String = runtime::get_named_arg("stake_package");
+            let amount: U256 = runtime::get_named_arg("amount");
+            let pool_id: u64 = runtime::get_named_arg("pool_id");
+
+            runtime::call_contract::<()>(
+                ContractHash::from_formatted_str(&token_hash).unwrap(),
+                "approve",
+                runtime_args! {
+                    "spender" => Key::from_formatted_str(&stake_package).unwrap(),
+                    "amount" => amount
+                },
+            );
+
+            runtime::call_contract::<U256>(
+                contract,
+                "deposit",
+                runtime_args! {
+                    "amount" => amount,
+                    "pool_id" => pool_id
+                },
+            );
+        }
+        1 => {
+            let pool_ids: Vec<u64> = runtime::get_named_arg("pool_ids");
+
+            runtime::call_contract::<Vec<U256>>(
+                contract,
+                "harvest_many",
+                runtime_args! {
+                    "pool_ids" => pool_ids
+                },
+            );
+        }
+        2 => {
+            let amount: U256 = runtime::get_named_arg("amount");
+            let pool_id: u64 = runtime::get_named_arg("pool_id");
+
+            runtime::call_contract::<(U256, U256)>(
+                contract,
+                "withdraw",
+                runtime_args! {
+                    "amount" => amount,
+                    "pool_id" => pool_id
+                },
+            );
+        }
+        3 => {
+            let staking_token: String = runtime::get_named_arg("staking_token");
+            let reward_token: String = runtime::get_named_arg("reward_token");
+            let start_time: u64 = runtime::get_named_arg("start_time");
+            let end_time: u64 = runtime::get_named_arg("end_time");
+            let precision: u64 = runtime::get_named_arg("precision");
+            let total_reward: U256 = runtime::get_named_arg("total_reward");
+            let stake_package: String = runtime::get_named_arg("stake_package");
+
+            runtime::call_contract::<()>(
+                ContractHash::from_formatted_str(&reward_token).unwrap(),
+                "approve",
+                runtime_args! {
+                    "spender" => Key::from_formatted_str(&stake_package).unwrap(),
+                    "amount" => total_reward
+                },
+            );
+
+            runtime::call_contract::<(ContractHash, ContractPackageHash)>(
+                contract,
+                "create_pool_contract",
+                runtime_args! {
+                    "staking_token" => staking_token,
+                    "reward_token" => reward_token,
+                    "start_time" => start_time,
+                    "end_time" => end_time,
+                    "precision" => precision,
+                    "total_reward" => total_reward
+                },
+            );
+        }
+        4 => {
+            let staking_token: String = runtime::get_named_arg("staking_token");
+            let start_time: u64 = runtime::get_named_arg("start_time");
+            let end_time: u64 = runtime::get_named_arg("end_time");
+            let precision: u64 = runtime::get_named_arg("precision");
+            let total_reward: U256 = runtime::get_named_arg("total_reward");
+
+            // the stored contract only ever sees this purse, never the main purse
+            let mut bytes = [0u8; 32];
+            total_reward.to_little_endian(&mut bytes);
+            let motes = U512::from_little_endian(&bytes);
+
+            let purse = system::create_purse();
+            system::transfer_from_purse_to_purse(account::get_main_purse(), purse, motes, None)
+                .unwrap_or_revert();
+
+            runtime::call_contract::<u64>(
+                contract,
+                "add_native_pool",
+                runtime_args! {
+                    "staking_token" => staking_token,
+                    "start_time" => start_time,
+                    "end_time" => end_time,
+                    "precision" => precision,
+                    "total_reward" => total_reward,
+                    "purse" => purse
+                },
+            );
+        }
+        _ => {}
+    }
+}
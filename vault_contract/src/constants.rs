@@ -0,0 +1,49 @@
+//! Constants used by the Vault contract.
+pub const CONTRACT_NAME: &str = "vault_contract";
+pub const CONTRACT_VERSION: &str = "CONT_VERSION_KEY";
+pub const CONTRACT_HASH: &str = "vault_conthash";
+
+pub const KEY_NAME_ADMIN: &str = "admin-account";
+pub const KEY_NAME_INITIALIZED: &str = "initialized";
+pub const KEY_NAME_REENTRANCY_LOCK: &str = "reentrancy_lock";
+pub const KEY_NAME_SELF_CONTRACT_HASH: &str = "self_contract_hash";
+pub const KEY_NAME_STAKE_CONTRACT: &str = "stake_contract";
+pub const KEY_NAME_STAKE_PACKAGE: &str = "stake_package";
+pub const KEY_NAME_POOL_ID: &str = "pool_id";
+pub const KEY_NAME_TOKEN_HASH: &str = "token_hash";
+pub const KEY_NAME_SHARES: &str = "shares";
+pub const KEY_NAME_TOTAL_SHARES: &str = "total_shares";
+pub const KEY_NAME_TOTAL_STAKED: &str = "total_staked";
+pub const KEY_NAME_TOTAL_IDLE: &str = "total_idle";
+
+pub const VAULT_PACKAGE_NAME: &str = "vault_pack";
+pub const VAULT_UREF_NAME: &str = "vault_access_uref";
+
+pub const ARG_NAME_ERC20_SELFCONTRACT_HASH: &str = "package-hash";
+pub const ARG_NAME_STAKE_CONTRACT: &str = "stake_contract";
+pub const ARG_NAME_STAKE_PACKAGE: &str = "stake_package";
+pub const ARG_NAME_POOL_ID: &str = "pool_id";
+pub const ARG_NAME_POOL_IDS: &str = "pool_ids";
+pub const ARG_NAME_AMOUNT: &str = "amount";
+pub const ARG_NAME_SHARES: &str = "shares";
+pub const ARG_NAME_ACCOUNT: &str = "account";
+
+pub const ENTRY_POINT_NAME_INIT: &str = "init";
+pub const ENTRY_POINT_NAME_DEPOSIT: &str = "deposit";
+pub const ENTRY_POINT_NAME_WITHDRAW: &str = "withdraw";
+pub const ENTRY_POINT_NAME_COMPOUND: &str = "compound";
+pub const ENTRY_POINT_NAME_GET_POSITION: &str = "get_position";
+
+// entry points of the staking contract
+pub const ENTRY_POINT_NAME_STAKE_DEPOSIT: &str = "deposit";
+pub const ENTRY_POINT_NAME_STAKE_WITHDRAW: &str = "withdraw";
+pub const ENTRY_POINT_NAME_STAKE_EMERGENCY_WITHDRAW: &str = "emergency_withdraw";
+pub const ENTRY_POINT_NAME_HARVEST_MANY: &str = "harvest_many";
+pub const ENTRY_POINT_NAME_GET_POOL: &str = "get_pool";
+
+pub const ENTRY_POINT_NAME_TRANSFER_FROM: &str = "transfer_from";
+pub const ENTRY_POINT_NAME_TRANSFER: &str = "transfer";
+pub const ENTRY_POINT_NAME_APPROVE: &str = "approve";
+pub const ARG_NAME_OWNER: &str = "owner";
+pub const ARG_NAME_SPENDER: &str = "spender";
+pub const ARG_NAME_RECIPIENT: &str = "recipient";
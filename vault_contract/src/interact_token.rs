@@ -0,0 +1,62 @@
+#![allow(non_camel_case_types)]
+
+extern crate alloc;
+
+use casper_contract::contract_api::runtime;
+use casper_types::{runtime_args, ContractHash, RuntimeArgs, U256};
+
+use crate::Address;
+
+use crate::constants::{
+    ARG_NAME_AMOUNT, ARG_NAME_OWNER, ARG_NAME_RECIPIENT, ARG_NAME_SPENDER,
+    ENTRY_POINT_NAME_APPROVE, ENTRY_POINT_NAME_TRANSFER, ENTRY_POINT_NAME_TRANSFER_FROM,
+};
+
+/*
+implement ERC20 functionality for the vault
+*/
+#[derive(Default)]
+pub struct interact_erc20;
+
+impl interact_erc20 {
+    pub fn transfer_from(
+        &mut self,
+        hash_token: ContractHash,
+        owner: Address,
+        recipient: Address,
+        amount: U256,
+    ) {
+        runtime::call_contract(
+            hash_token,
+            ENTRY_POINT_NAME_TRANSFER_FROM,
+            runtime_args! {
+                ARG_NAME_OWNER => owner,
+                ARG_NAME_RECIPIENT => recipient,
+                ARG_NAME_AMOUNT => amount
+            },
+        )
+    }
+
+    pub fn transfer(&mut self, hash_token: ContractHash, recipient: Address, amount: U256) {
+        runtime::call_contract(
+            hash_token,
+            ENTRY_POINT_NAME_TRANSFER,
+            runtime_args! {
+                ARG_NAME_RECIPIENT => recipient,
+                ARG_NAME_AMOUNT => amount
+            },
+        )
+    }
+
+    // lets spender move amount of the vault's tokens
+    pub fn approve(&mut self, hash_token: ContractHash, spender: Address, amount: U256) {
+        runtime::call_contract(
+            hash_token,
+            ENTRY_POINT_NAME_APPROVE,
+            runtime_args! {
+                ARG_NAME_SPENDER => spender,
+                ARG_NAME_AMOUNT => amount
+            },
+        )
+    }
+}
@@ -0,0 +1,50 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod constants;
+pub mod interact_stake;
+pub mod interact_token;
+pub mod utils;
+
+mod vault;
+pub use vault::VaultContract;
+
+mod address;
+pub use address::Address;
+
+use casper_types::ApiError;
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum Error {
+    /// Account does not own enough shares.
+    InsufficientShares,
+    /// Operation would cause an integer overflow.
+    Overflow,
+    /// Entry point called while another one is running.
+    Reentrant,
+    /// Operation would cause an integer underflow.
+    Underflow,
+    /// Caller could not be determined from the call stack.
+    InvalidContext,
+    /// User error.
+    User(u16),
+}
+
+const ERROR_INSUFFICIENT_SHARES: u16 = u16::MAX;
+const ERROR_OVERFLOW: u16 = u16::MAX - 1;
+const ERROR_REENTRANT: u16 = u16::MAX - 2;
+const ERROR_UNDERFLOW: u16 = u16::MAX - 3;
+const ERROR_INVALID_CONTEXT: u16 = u16::MAX - 4;
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        let user_error = match error {
+            Error::InsufficientShares => ERROR_INSUFFICIENT_SHARES,
+            Error::Overflow => ERROR_OVERFLOW,
+            Error::Reentrant => ERROR_REENTRANT,
+            Error::Underflow => ERROR_UNDERFLOW,
+            Error::InvalidContext => ERROR_INVALID_CONTEXT,
+            Error::User(user_error) => user_error,
+        };
+        ApiError::User(user_error)
+    }
+}
@@ -0,0 +1,165 @@
+#![no_std]
+#![no_main]
+
+// We need to explicitly import the std alloc crate and `alloc::string::String` as we're in a
+// `no_std` environment.
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String, vec};
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    contracts::{EntryPoint, EntryPointAccess, EntryPointType, EntryPoints},
+    CLType, CLTyped, CLValue, ContractHash, ContractPackageHash, Key, Parameter, U256,
+};
+
+use vault_contract::constants::{
+    ARG_NAME_ACCOUNT, ARG_NAME_AMOUNT, ARG_NAME_ERC20_SELFCONTRACT_HASH, ARG_NAME_POOL_ID,
+    ARG_NAME_SHARES, ARG_NAME_STAKE_CONTRACT, ARG_NAME_STAKE_PACKAGE, CONTRACT_HASH, CONTRACT_NAME,
+    CONTRACT_VERSION, ENTRY_POINT_NAME_COMPOUND, ENTRY_POINT_NAME_DEPOSIT,
+    ENTRY_POINT_NAME_GET_POSITION, ENTRY_POINT_NAME_INIT, ENTRY_POINT_NAME_WITHDRAW,
+    KEY_NAME_ADMIN, KEY_NAME_INITIALIZED, KEY_NAME_REENTRANCY_LOCK, VAULT_PACKAGE_NAME,
+    VAULT_UREF_NAME,
+};
+use vault_contract::{Address, VaultContract};
+
+// All the calls and their arg names are compiled into Entry Points for the runtime.
+// #[no_mangle] macro ensures that the function name will be the same string in WASM.
+#[no_mangle]
+pub extern "C" fn call() {
+    let counter_local_key = storage::new_uref(0_i32);
+
+    let mut vault_named_keys: BTreeMap<String, Key> = BTreeMap::new();
+    let key_name = String::from(CONTRACT_NAME);
+    vault_named_keys.insert(key_name, counter_local_key.into());
+
+    let admin_key = storage::new_uref(runtime::get_caller());
+    vault_named_keys.insert(String::from(KEY_NAME_ADMIN), admin_key.into());
+
+    let initialized_key = storage::new_uref(false);
+    vault_named_keys.insert(String::from(KEY_NAME_INITIALIZED), initialized_key.into());
+
+    let reentrancy_lock_key = storage::new_uref(false);
+    vault_named_keys.insert(
+        String::from(KEY_NAME_REENTRANCY_LOCK),
+        reentrancy_lock_key.into(),
+    );
+
+    let (stored_contract_hash, contract_version) = storage::new_locked_contract(
+        get_entry_points(),
+        Some(vault_named_keys),
+        Some(String::from(VAULT_PACKAGE_NAME)),
+        Some(String::from(VAULT_UREF_NAME)),
+    );
+
+    let version_uref = storage::new_uref(contract_version);
+
+    runtime::put_key(CONTRACT_VERSION, version_uref.into());
+
+    runtime::put_key(CONTRACT_HASH, stored_contract_hash.into());
+}
+
+#[no_mangle]
+pub extern "C" fn init() {
+    let contract_package_hash: Key = runtime::get_named_arg(ARG_NAME_ERC20_SELFCONTRACT_HASH);
+    let p_hash = ContractPackageHash::new(contract_package_hash.into_hash().unwrap_or_revert());
+
+    let stake_contract_string: String = runtime::get_named_arg(ARG_NAME_STAKE_CONTRACT);
+    let stake_contract = ContractHash::from_formatted_str(stake_contract_string.as_str())
+        .expect("stake contract hash string format is error");
+
+    let stake_package: Key = runtime::get_named_arg(ARG_NAME_STAKE_PACKAGE);
+    let stake_package = ContractPackageHash::new(stake_package.into_hash().unwrap_or_revert());
+
+    let pool_id: u64 = runtime::get_named_arg(ARG_NAME_POOL_ID);
+
+    VaultContract::default().init(p_hash, stake_contract, stake_package, pool_id);
+}
+
+#[no_mangle]
+pub extern "C" fn deposit() {
+    let amount: U256 = runtime::get_named_arg(ARG_NAME_AMOUNT);
+
+    let shares = VaultContract::default().deposit(amount);
+
+    runtime::ret(CLValue::from_t(shares).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn withdraw() {
+    let shares: U256 = runtime::get_named_arg(ARG_NAME_SHARES);
+
+    let amount = VaultContract::default().withdraw(shares);
+
+    runtime::ret(CLValue::from_t(amount).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn compound() {
+    let staked = VaultContract::default().compound();
+
+    runtime::ret(CLValue::from_t(staked).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn get_position() {
+    let account: Address = runtime::get_named_arg(ARG_NAME_ACCOUNT);
+
+    let position = VaultContract::default().get_position(account);
+
+    runtime::ret(CLValue::from_t(position).unwrap_or_revert())
+}
+
+fn get_entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_NAME_INIT,
+        vec![
+            Parameter::new(ARG_NAME_ERC20_SELFCONTRACT_HASH, Key::cl_type()),
+            Parameter::new(ARG_NAME_STAKE_CONTRACT, String::cl_type()),
+            Parameter::new(ARG_NAME_STAKE_PACKAGE, Key::cl_type()),
+            Parameter::new(ARG_NAME_POOL_ID, u64::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_NAME_DEPOSIT,
+        vec![Parameter::new(ARG_NAME_AMOUNT, U256::cl_type())],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_NAME_WITHDRAW,
+        vec![Parameter::new(ARG_NAME_SHARES, U256::cl_type())],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_NAME_COMPOUND,
+        vec![],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_NAME_GET_POSITION,
+        vec![Parameter::new(ARG_NAME_ACCOUNT, Key::cl_type())],
+        <(U256, U256)>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points
+}
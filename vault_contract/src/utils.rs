@@ -0,0 +1,109 @@
+extern crate alloc;
+
+use core::convert::TryInto;
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+
+use casper_types::{
+    api_error::ApiError,
+    bytesrepr::{FromBytes, ToBytes},
+    system::CallStackElement,
+    CLTyped, URef, U256, U512,
+};
+
+use crate::{constants::KEY_NAME_REENTRANCY_LOCK, Address, Error};
+
+pub fn get_key<T: FromBytes + CLTyped>(name: &str) -> T {
+    let key: URef = runtime::get_key(name)
+        .unwrap_or_revert_with(ApiError::MissingKey)
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+    storage::read(key)
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_revert_with(ApiError::ValueNotFound)
+}
+
+pub fn set_key<T: ToBytes + CLTyped>(name: &str, value: T) {
+    match runtime::get_key(name) {
+        Some(key) => {
+            let key_ref = key.try_into().unwrap_or_revert();
+            storage::write(key_ref, value);
+        }
+        None => {
+            let key = storage::new_uref(value).into();
+            runtime::put_key(name, key);
+        }
+    }
+}
+
+// contract-wide reentrancy lock, mutating entry points hold it
+// while they run so token contracts can't call back into them
+pub struct ReentrancyGuard;
+
+impl ReentrancyGuard {
+    pub fn enter() -> Self {
+        if get_key::<bool>(KEY_NAME_REENTRANCY_LOCK) {
+            runtime::revert(Error::Reentrant)
+        }
+
+        set_key(KEY_NAME_REENTRANCY_LOCK, true);
+
+        ReentrancyGuard
+    }
+}
+
+impl Drop for ReentrancyGuard {
+    // released when the entry point returns, a revert
+    // discards the lock along with everything else
+    fn drop(&mut self) {
+        set_key(KEY_NAME_REENTRANCY_LOCK, false);
+    }
+}
+
+// whoever called the vault directly: the account of a deploy,
+// or the package of a contract that calls the vault itself
+pub fn get_immediate_caller() -> Address {
+    match runtime::get_call_stack().into_iter().rev().nth(1) {
+        Some(CallStackElement::Session { account_hash }) => Address::from(account_hash),
+        Some(CallStackElement::StoredSession { account_hash, .. }) => Address::from(account_hash),
+        Some(CallStackElement::StoredContract {
+            contract_package_hash,
+            ..
+        }) => Address::from(contract_package_hash),
+        None => runtime::revert(Error::InvalidContext),
+    }
+}
+
+pub fn add(a: U256, b: U256) -> Result<U256, Error> {
+    a.checked_add(b).ok_or(Error::Overflow)
+}
+
+pub fn sub(a: U256, b: U256) -> Result<U256, Error> {
+    a.checked_sub(b).ok_or(Error::Underflow)
+}
+
+// a * b / c without overflowing on the intermediate product
+pub fn mul_div(a: U256, b: U256, c: U256) -> Result<U256, Error> {
+    if c.is_zero() {
+        return Err(Error::Overflow);
+    }
+
+    let value = u256_to_u512(a) * u256_to_u512(b) / u256_to_u512(c);
+
+    if value.bits() > 256 {
+        return Err(Error::Overflow);
+    }
+
+    let mut bytes = [0_u8; 64];
+    value.to_little_endian(&mut bytes);
+    Ok(U256::from_little_endian(&bytes[..32]))
+}
+
+fn u256_to_u512(value: U256) -> U512 {
+    let mut bytes = [0_u8; 32];
+    value.to_little_endian(&mut bytes);
+    U512::from_little_endian(&bytes)
+}
@@ -0,0 +1,331 @@
+extern crate alloc;
+
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+};
+
+use casper_contract::{
+    contract_api::{
+        runtime::{self, revert},
+        storage,
+    },
+    unwrap_or_revert::UnwrapOrRevert,
+};
+
+use casper_types::{ApiError, ContractHash, ContractPackageHash, U256};
+
+use crate::{interact_stake::interact_stake, interact_token::interact_erc20, utils};
+use crate::{Address, Error};
+
+use crate::constants::{
+    KEY_NAME_INITIALIZED, KEY_NAME_POOL_ID, KEY_NAME_SELF_CONTRACT_HASH, KEY_NAME_SHARES,
+    KEY_NAME_STAKE_CONTRACT, KEY_NAME_STAKE_PACKAGE, KEY_NAME_TOKEN_HASH, KEY_NAME_TOTAL_IDLE,
+    KEY_NAME_TOTAL_SHARES, KEY_NAME_TOTAL_STAKED,
+};
+
+#[derive(Default)]
+pub struct VaultContract;
+
+impl VaultContract {
+    // init the vault on a pool of the staking contract,
+    // the pool has to pay rewards in its staking token
+    pub fn init(
+        &self,
+        conthash: ContractPackageHash,
+        stake_contract: ContractHash,
+        stake_package: ContractPackageHash,
+        pool_id: u64,
+    ) {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        let is_initialized: bool = utils::get_key(KEY_NAME_INITIALIZED);
+
+        if is_initialized {
+            revert(ApiError::PermissionDenied)
+        }
+
+        let (keys, values) = interact_stake::default().get_pool(stake_contract, pool_id);
+
+        let staking_token = *keys.get("staking_token").unwrap_or_revert();
+        let reward_token = *keys.get("reward_token").unwrap_or_revert();
+        let cooldown = *values.get("cooldown").unwrap_or_revert();
+
+        // rewards are restaked, and withdrawals
+        // have to leave the pool right away
+        if staking_token != reward_token || !cooldown.is_zero() {
+            revert(ApiError::InvalidArgument)
+        }
+
+        let token_hash = ContractHash::new(staking_token.into_hash().unwrap_or_revert());
+
+        storage::new_dictionary(KEY_NAME_SHARES).unwrap_or_revert();
+
+        utils::set_key(KEY_NAME_SELF_CONTRACT_HASH, conthash);
+        utils::set_key(KEY_NAME_STAKE_CONTRACT, stake_contract);
+        utils::set_key(KEY_NAME_STAKE_PACKAGE, stake_package);
+        utils::set_key(KEY_NAME_POOL_ID, pool_id);
+        utils::set_key(KEY_NAME_TOKEN_HASH, token_hash);
+        utils::set_key(KEY_NAME_TOTAL_SHARES, U256::zero());
+        utils::set_key(KEY_NAME_TOTAL_STAKED, U256::zero());
+        utils::set_key(KEY_NAME_TOTAL_IDLE, U256::zero());
+        utils::set_key(KEY_NAME_INITIALIZED, true);
+    }
+
+    // stakes amount of the caller's tokens through the vault,
+    // returns the shares minted for them
+    pub fn deposit(&self, amount: U256) -> U256 {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        if amount.is_zero() {
+            revert(ApiError::InvalidArgument)
+        }
+
+        // price the new shares with every reward earned so far
+        compound();
+
+        let (_, values) = interact_stake::default().get_pool(stake_contract(), pool_id());
+
+        if !accepts_stake(&values) {
+            revert(ApiError::InvalidArgument)
+        }
+
+        let total_shares: U256 = utils::get_key(KEY_NAME_TOTAL_SHARES);
+        let total_assets = total_assets();
+
+        let account = utils::get_immediate_caller();
+
+        interact_erc20::default().transfer_from(token_hash(), account, self_address(), amount);
+
+        let staked = stake(amount);
+
+        let shares = if total_shares.is_zero() || total_assets.is_zero() {
+            staked
+        } else {
+            utils::mul_div(staked, total_shares, total_assets).unwrap_or_revert()
+        };
+
+        if shares.is_zero() {
+            revert(ApiError::InvalidArgument)
+        }
+
+        let total_staked: U256 = utils::get_key(KEY_NAME_TOTAL_STAKED);
+        utils::set_key(
+            KEY_NAME_TOTAL_STAKED,
+            utils::add(total_staked, staked).unwrap_or_revert(),
+        );
+        utils::set_key(
+            KEY_NAME_TOTAL_SHARES,
+            utils::add(total_shares, shares).unwrap_or_revert(),
+        );
+
+        save_shares(
+            account,
+            utils::add(get_shares(account), shares).unwrap_or_revert(),
+        );
+
+        shares
+    }
+
+    // burns shares of the caller and sends back the tokens
+    // they are worth, returns the amount sent
+    pub fn withdraw(&self, shares: U256) -> U256 {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        if shares.is_zero() {
+            revert(ApiError::InvalidArgument)
+        }
+
+        let account = utils::get_immediate_caller();
+        let balance = get_shares(account);
+
+        if shares > balance {
+            revert(Error::InsufficientShares)
+        }
+
+        compound();
+
+        let total_shares: U256 = utils::get_key(KEY_NAME_TOTAL_SHARES);
+        let amount = utils::mul_div(shares, total_assets(), total_shares).unwrap_or_revert();
+
+        save_shares(account, utils::sub(balance, shares).unwrap_or_revert());
+        utils::set_key(
+            KEY_NAME_TOTAL_SHARES,
+            utils::sub(total_shares, shares).unwrap_or_revert(),
+        );
+
+        // rewards waiting in the vault are paid out first
+        let mut total_idle: U256 = utils::get_key(KEY_NAME_TOTAL_IDLE);
+        let from_idle = total_idle.min(amount);
+        let from_stake = utils::sub(amount, from_idle).unwrap_or_revert();
+        total_idle = utils::sub(total_idle, from_idle).unwrap_or_revert();
+
+        if !from_stake.is_zero() {
+            let (_, rewards) =
+                interact_stake::default().withdraw(stake_contract(), pool_id(), from_stake);
+
+            let total_staked: U256 = utils::get_key(KEY_NAME_TOTAL_STAKED);
+            utils::set_key(
+                KEY_NAME_TOTAL_STAKED,
+                utils::sub(total_staked, from_stake).unwrap_or_revert(),
+            );
+
+            total_idle = utils::add(total_idle, rewards).unwrap_or_revert();
+        }
+
+        utils::set_key(KEY_NAME_TOTAL_IDLE, total_idle);
+
+        interact_erc20::default().transfer(token_hash(), account, amount);
+
+        amount
+    }
+
+    // harvests the vault's rewards and stakes them again,
+    // returns the amount restaked. Callable by anyone
+    pub fn compound(&self) -> U256 {
+        let _lock = utils::ReentrancyGuard::enter();
+
+        compound()
+    }
+
+    // shares of an account and the tokens they are
+    // worth as of the last compound
+    pub fn get_position(&self, account: Address) -> (U256, U256) {
+        let shares = get_shares(account);
+        let total_shares: U256 = utils::get_key(KEY_NAME_TOTAL_SHARES);
+
+        if shares.is_zero() {
+            return (shares, U256::zero());
+        }
+
+        let amount = utils::mul_div(shares, total_assets(), total_shares).unwrap_or_revert();
+
+        (shares, amount)
+    }
+}
+
+// harvests and restakes, for entry points that already hold the lock
+fn compound() -> U256 {
+    let total_staked: U256 = utils::get_key(KEY_NAME_TOTAL_STAKED);
+    let mut total_idle: U256 = utils::get_key(KEY_NAME_TOTAL_IDLE);
+
+    let (_, values) = interact_stake::default().get_pool(stake_contract(), pool_id());
+
+    // emergency mode closes harvests and withdrawals, so the vault takes
+    // its principal out with emergency_withdraw and forfeits the pending
+    // rewards. Shares are paid out of the idle tokens from then on
+    if !values.get("emergency").unwrap_or_revert().is_zero() {
+        if !total_staked.is_zero() {
+            let principal =
+                interact_stake::default().emergency_withdraw(stake_contract(), pool_id());
+
+            utils::set_key(KEY_NAME_TOTAL_STAKED, U256::zero());
+            utils::set_key(
+                KEY_NAME_TOTAL_IDLE,
+                utils::add(total_idle, principal).unwrap_or_revert(),
+            );
+        }
+
+        return U256::zero();
+    }
+
+    // nothing to harvest without a stake
+    if !total_staked.is_zero() {
+        total_idle = utils::add(
+            total_idle,
+            interact_stake::default().harvest(stake_contract(), pool_id()),
+        )
+        .unwrap_or_revert();
+    }
+
+    if total_idle.is_zero() || !accepts_stake(&values) {
+        utils::set_key(KEY_NAME_TOTAL_IDLE, total_idle);
+        return U256::zero();
+    }
+
+    let staked = stake(total_idle);
+
+    utils::set_key(
+        KEY_NAME_TOTAL_STAKED,
+        utils::add(total_staked, staked).unwrap_or_revert(),
+    );
+    utils::set_key(KEY_NAME_TOTAL_IDLE, U256::zero());
+
+    staked
+}
+
+// stakes amount of the vault's tokens in its pool,
+// returns the amount the pool credited
+fn stake(amount: U256) -> U256 {
+    let stake_package: ContractPackageHash = utils::get_key(KEY_NAME_STAKE_PACKAGE);
+
+    interact_erc20::default().approve(token_hash(), Address::from(stake_package), amount);
+
+    interact_stake::default().deposit(stake_contract(), pool_id(), amount)
+}
+
+// ended pools and pools in emergency mode take no deposits, and a
+// pool with a cooldown would hold withdrawals back, so harvested
+// rewards wait in the vault
+fn accepts_stake(values: &BTreeMap<String, U256>) -> bool {
+    let end_time = *values.get("end_time").unwrap_or_revert();
+    let cooldown = *values.get("cooldown").unwrap_or_revert();
+    let emergency = *values.get("emergency").unwrap_or_revert();
+    let now: u64 = runtime::get_blocktime().into();
+
+    end_time >= U256::from(now) && cooldown.is_zero() && emergency.is_zero()
+}
+
+// staked tokens plus harvested rewards not staked yet
+fn total_assets() -> U256 {
+    let total_staked: U256 = utils::get_key(KEY_NAME_TOTAL_STAKED);
+    let total_idle: U256 = utils::get_key(KEY_NAME_TOTAL_IDLE);
+
+    utils::add(total_staked, total_idle).unwrap_or_revert()
+}
+
+// share balances are keyed by the hex of the
+// account hash or contract package hash
+fn shares_key(account: Address) -> String {
+    match account {
+        Address::Account(account_hash) => account_hash.to_string(),
+        Address::Contract(contract_package_hash) => contract_package_hash.to_string(),
+    }
+}
+
+fn get_shares(account: Address) -> U256 {
+    let seed_uref = *runtime::get_key(KEY_NAME_SHARES)
+        .unwrap_or_revert()
+        .as_uref()
+        .unwrap_or_revert();
+
+    storage::dictionary_get::<U256>(seed_uref, &shares_key(account))
+        .unwrap_or_revert()
+        .unwrap_or_default()
+}
+
+fn save_shares(account: Address, shares: U256) {
+    let seed_uref = *runtime::get_key(KEY_NAME_SHARES)
+        .unwrap_or_revert()
+        .as_uref()
+        .unwrap_or_revert();
+
+    storage::dictionary_put(seed_uref, &shares_key(account), shares);
+}
+
+fn self_address() -> Address {
+    let conthash: ContractPackageHash = utils::get_key(KEY_NAME_SELF_CONTRACT_HASH);
+    Address::from(conthash)
+}
+
+fn stake_contract() -> ContractHash {
+    utils::get_key(KEY_NAME_STAKE_CONTRACT)
+}
+
+fn token_hash() -> ContractHash {
+    utils::get_key(KEY_NAME_TOKEN_HASH)
+}
+
+fn pool_id() -> u64 {
+    utils::get_key(KEY_NAME_POOL_ID)
+}